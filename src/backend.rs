@@ -0,0 +1,213 @@
+use crate::{read_bytes, DmaError};
+use memprocfs::{Vmm, CONFIG_OPT_PROCESS_DTB};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+/// The memory, module and VFS operations the crate performs on a `Vmm`.
+///
+/// Functions generic over this trait can run against a live `Vmm` or against
+/// [`MockMemory`], so their logic can be tested without a DMA device.
+///
+/// # Examples
+///
+/// ```ignore
+/// fn read_health<M: MemoryReader + ?Sized>(reader: &M, pid: u32, player: u64) -> Result<i32, DmaError> {
+///     let bytes = reader.read_memory(pid, player + 0x100, 4)?;
+///     Ok(i32::from_le_bytes(bytes.try_into().unwrap()))
+/// }
+/// ```
+pub trait MemoryReader {
+    /// Reads exactly `len` bytes from the memory of the process `pid`.
+    fn read_memory(&self, pid: u32, addr: u64, len: usize) -> Result<Vec<u8>, DmaError>;
+
+    /// Writes `bytes` to the memory of the process `pid`.
+    fn write_memory(&self, pid: u32, addr: u64, bytes: &[u8]) -> Result<(), DmaError>;
+
+    /// Returns the base address of a module loaded in the process `pid`.
+    fn module_base(&self, pid: u32, module_name: &str) -> Result<u64, DmaError>;
+
+    /// Reads up to `max_len` bytes of a file in the memprocfs virtual file system.
+    fn vfs_read(&self, path: &str, max_len: u32) -> Result<Vec<u8>, DmaError>;
+
+    /// Overrides the DTB (CR3) used to translate addresses of the process `pid`.
+    fn set_process_dtb(&self, pid: u32, dtb: u64) -> Result<(), DmaError>;
+}
+
+impl MemoryReader for Vmm<'_> {
+    fn read_memory(&self, pid: u32, addr: u64, len: usize) -> Result<Vec<u8>, DmaError> {
+        let process = self
+            .process_from_pid(pid)
+            .map_err(|_| DmaError::ProcessNotFound(format!("with PID {}", pid)))?;
+        read_bytes(&process, addr, len)
+    }
+
+    fn write_memory(&self, pid: u32, addr: u64, bytes: &[u8]) -> Result<(), DmaError> {
+        let process = self
+            .process_from_pid(pid)
+            .map_err(|_| DmaError::ProcessNotFound(format!("with PID {}", pid)))?;
+        process
+            .mem_write(addr, bytes)
+            .map_err(|_| DmaError::Write { addr, len: bytes.len() })
+    }
+
+    fn module_base(&self, pid: u32, module_name: &str) -> Result<u64, DmaError> {
+        let process = self
+            .process_from_pid(pid)
+            .map_err(|_| DmaError::ProcessNotFound(format!("with PID {}", pid)))?;
+        process
+            .get_module_base(module_name)
+            .map_err(|_| DmaError::ModuleNotFound(module_name.to_string()))
+    }
+
+    fn vfs_read(&self, path: &str, max_len: u32) -> Result<Vec<u8>, DmaError> {
+        Vmm::vfs_read(self, path, max_len, 0).map_err(|e| DmaError::Vfs(e.to_string()))
+    }
+
+    fn set_process_dtb(&self, pid: u32, dtb: u64) -> Result<(), DmaError> {
+        self.set_config(CONFIG_OPT_PROCESS_DTB | pid as u64, dtb)
+            .map_err(|e| DmaError::InvalidConfig(format!("DTB 0x{:X} for PID {}: {}", dtb, pid, e)))
+    }
+}
+
+/// An in-memory [`MemoryReader`] for tests.
+///
+/// Memory is made of regions added with [`with_memory`](MockMemory::with_memory);
+/// reads and writes must lie entirely within one region. A module registered with
+/// [`with_dtb`](MockMemory::with_dtb) set for its process only resolves once that DTB
+/// has been applied, which mirrors how modules behave when CR3 is hidden.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mock = MockMemory::new()
+///     .with_memory(4, 0x1000, vec![0x4D, 0x5A])
+///     .with_module(4, "ntoskrnl.exe", 0x1000);
+/// assert_eq!(find_base_address(&mock, 4, "ntoskrnl.exe"), Some(0x1000));
+/// ```
+#[derive(Debug, Default)]
+pub struct MockMemory {
+    memory: Mutex<HashMap<u32, BTreeMap<u64, Vec<u8>>>>,
+    modules: HashMap<(u32, String), u64>,
+    files: HashMap<String, Vec<u8>>,
+    required_dtbs: HashMap<u32, u64>,
+    dtbs: Mutex<HashMap<u32, u64>>,
+}
+
+impl MockMemory {
+    /// Creates an empty mock with no processes, modules or files.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a readable and writable region of process memory starting at `addr`.
+    pub fn with_memory(self, pid: u32, addr: u64, bytes: Vec<u8>) -> Self {
+        self.memory.lock().unwrap().entry(pid).or_default().insert(addr, bytes);
+        self
+    }
+
+    /// Adds a module loaded at `base` in process `pid`; the name is matched case-insensitively.
+    pub fn with_module(mut self, pid: u32, module_name: &str, base: u64) -> Self {
+        self.modules.insert((pid, module_name.to_lowercase()), base);
+        self
+    }
+
+    /// Adds a file to the virtual file system.
+    pub fn with_file(mut self, path: &str, contents: &[u8]) -> Self {
+        self.files.insert(path.to_string(), contents.to_vec());
+        self
+    }
+
+    /// Makes the modules of process `pid` resolve only after `dtb` was applied.
+    pub fn with_dtb(mut self, pid: u32, dtb: u64) -> Self {
+        self.required_dtbs.insert(pid, dtb);
+        self
+    }
+
+    /// Returns the DTB last applied to process `pid`, if any.
+    pub fn dtb(&self, pid: u32) -> Option<u64> {
+        self.dtbs.lock().unwrap().get(&pid).copied()
+    }
+
+    /// Runs `f` on the region containing `addr..addr + len`, if there is one.
+    fn with_region<T>(&self, pid: u32, addr: u64, len: usize, f: impl FnOnce(&mut [u8]) -> T) -> Option<T> {
+        let mut memory = self.memory.lock().unwrap();
+        let (start, region) = memory.get_mut(&pid)?.range_mut(..=addr).next_back()?;
+        let offset = (addr - start) as usize;
+        let end = offset.checked_add(len)?;
+        region.get_mut(offset..end).map(f)
+    }
+}
+
+impl MemoryReader for MockMemory {
+    fn read_memory(&self, pid: u32, addr: u64, len: usize) -> Result<Vec<u8>, DmaError> {
+        self.with_region(pid, addr, len, |bytes| bytes.to_vec())
+            .ok_or(DmaError::Read { addr, len })
+    }
+
+    fn write_memory(&self, pid: u32, addr: u64, bytes: &[u8]) -> Result<(), DmaError> {
+        self.with_region(pid, addr, bytes.len(), |region| region.copy_from_slice(bytes))
+            .ok_or(DmaError::Write { addr, len: bytes.len() })
+    }
+
+    fn module_base(&self, pid: u32, module_name: &str) -> Result<u64, DmaError> {
+        let not_found = || DmaError::ModuleNotFound(module_name.to_string());
+        if let Some(required) = self.required_dtbs.get(&pid) {
+            if self.dtb(pid) != Some(*required) {
+                return Err(not_found());
+            }
+        }
+        self.modules
+            .get(&(pid, module_name.to_lowercase()))
+            .copied()
+            .ok_or_else(not_found)
+    }
+
+    fn vfs_read(&self, path: &str, max_len: u32) -> Result<Vec<u8>, DmaError> {
+        let contents = self
+            .files
+            .get(path)
+            .ok_or_else(|| DmaError::Vfs(format!("{} not found", path)))?;
+        Ok(contents[..contents.len().min(max_len as usize)].to_vec())
+    }
+
+    fn set_process_dtb(&self, pid: u32, dtb: u64) -> Result<(), DmaError> {
+        self.dtbs.lock().unwrap().insert(pid, dtb);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_within_a_region() {
+        let mock = MockMemory::new().with_memory(1, 0x1000, vec![1, 2, 3, 4]);
+        assert_eq!(mock.read_memory(1, 0x1001, 2).unwrap(), vec![2, 3]);
+        assert_eq!(mock.read_memory(1, 0x1000, 4).unwrap(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn rejects_reads_outside_a_region() {
+        let mock = MockMemory::new().with_memory(1, 0x1000, vec![0; 4]);
+        assert!(matches!(mock.read_memory(1, 0x1002, 4), Err(DmaError::Read { addr: 0x1002, len: 4 })));
+        assert!(mock.read_memory(1, 0xFFF, 1).is_err());
+        assert!(mock.read_memory(2, 0x1000, 1).is_err());
+    }
+
+    #[test]
+    fn writes_are_visible_to_reads() {
+        let mock = MockMemory::new().with_memory(1, 0x1000, vec![0; 4]);
+        mock.write_memory(1, 0x1002, &[0xAA, 0xBB]).unwrap();
+        assert_eq!(mock.read_memory(1, 0x1000, 4).unwrap(), vec![0, 0, 0xAA, 0xBB]);
+        assert!(mock.write_memory(1, 0x1003, &[0, 0]).is_err());
+    }
+
+    #[test]
+    fn modules_require_the_configured_dtb() {
+        let mock = MockMemory::new().with_module(1, "Game.exe", 0x4000).with_dtb(1, 0x1AB000);
+        assert!(mock.module_base(1, "game.exe").is_err());
+        mock.set_process_dtb(1, 0x1AB000).unwrap();
+        assert_eq!(mock.module_base(1, "game.exe").unwrap(), 0x4000);
+    }
+}
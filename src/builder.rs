@@ -0,0 +1,195 @@
+use crate::device::fpga_device_config;
+use crate::DmaError;
+use memprocfs::Vmm;
+use std::path::Path;
+
+/// Builds a `Vmm` from typed options instead of a raw memprocfs argument vector.
+///
+/// # Examples
+///
+/// ```ignore
+/// let vmm = VmmBuilder::new()
+///     .device("fpga")
+///     .memmap_auto()
+///     .disable_symbols()
+///     .build("vmm.dll")?;
+/// ```
+///
+/// # Multiple devices
+///
+/// Every built `Vmm` has its own memprocfs handle, with its own process list, caches
+/// and refresh state, so two instances on two FPGA cards can be used at the same time.
+/// Select each card with [`fpga_device`](Self::fpga_device) and find the indices with
+/// [`list_fpga_devices`](crate::list_fpga_devices). Keep the crate's per-target state,
+/// such as [`DtbCache`](crate::DtbCache) or [`SharedVmm`](crate::SharedVmm), separate for
+/// each instance, and don't save a memory map from one target for use with the other.
+///
+/// ```ignore
+/// let first = VmmBuilder::new().fpga_device(0).build("vmm.dll")?;
+/// let second = VmmBuilder::new().fpga_device(1).build("vmm.dll")?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct VmmBuilder {
+    device: Option<String>,
+    memmap: Option<String>,
+    memmap_auto: bool,
+    disable_symbols: bool,
+    verbose: bool,
+    extra_args: Vec<String>,
+}
+
+impl VmmBuilder {
+    /// Creates a builder with no options set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the memory acquisition device, e.g. `fpga` (`-device`).
+    pub fn device(mut self, device: &str) -> Self {
+        self.device = Some(device.to_string());
+        self
+    }
+
+    /// Selects the FPGA device at `index` when several are connected (`-device fpga://devindex=<index>`).
+    ///
+    /// Other LeechCore FPGA parameters can still be passed with [`device`](Self::device),
+    /// e.g. `fpga://devindex=1,algo=0`.
+    pub fn fpga_device(self, index: u32) -> Self {
+        let device = fpga_device_config(index);
+        self.device(&device)
+    }
+
+    /// Uses a memory map file to restrict physical memory reads (`-memmap <path>`).
+    ///
+    /// A memory map saved from a previous session with [`save_memory_map`](crate::save_memory_map)
+    /// makes startup faster and avoids reads of unbacked physical ranges, which can hang
+    /// some FPGA devices. With reliable physical reads memprocfs usually finds the correct
+    /// DTB itself, so [`fix_cr3`](crate::fix_cr3) is then only needed for processes whose
+    /// CR3 is deliberately hidden.
+    pub fn memmap(mut self, path: impl AsRef<Path>) -> Self {
+        self.memmap = Some(path.as_ref().display().to_string());
+        self
+    }
+
+    /// Lets memprocfs generate the memory map from the target (`-memmap auto`).
+    ///
+    /// See [`memmap`](Self::memmap) for using a saved memory map instead.
+    pub fn memmap_auto(mut self) -> Self {
+        self.memmap_auto = true;
+        self
+    }
+
+    /// Disables debug symbol (PDB) lookups (`-disable-symbols`).
+    pub fn disable_symbols(mut self) -> Self {
+        self.disable_symbols = true;
+        self
+    }
+
+    /// Enables verbose memprocfs console output (`-printf -v`).
+    pub fn verbose(mut self) -> Self {
+        self.verbose = true;
+        self
+    }
+
+    /// Appends a raw memprocfs argument not covered by the typed options.
+    pub fn arg(mut self, arg: &str) -> Self {
+        self.extra_args.push(arg.to_string());
+        self
+    }
+
+    /// Validates the options and assembles the memprocfs argument vector.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the arguments, or `DmaError::InvalidConfig` if no device was
+    /// set or mutually exclusive options were combined.
+    pub fn args(&self) -> Result<Vec<String>, DmaError> {
+        let device = self
+            .device
+            .as_ref()
+            .ok_or_else(|| DmaError::InvalidConfig("no device set".to_string()))?;
+        if self.memmap.is_some() && self.memmap_auto {
+            return Err(DmaError::InvalidConfig("memmap and memmap_auto are mutually exclusive".to_string()));
+        }
+
+        let mut args = vec![String::new(), "-device".to_string(), device.clone()];
+        if let Some(memmap) = &self.memmap {
+            args.extend(["-memmap".to_string(), memmap.clone()]);
+        }
+        if self.memmap_auto {
+            args.extend(["-memmap".to_string(), "auto".to_string()]);
+        }
+        if self.disable_symbols {
+            args.push("-disable-symbols".to_string());
+        }
+        if self.verbose {
+            args.extend(["-printf".to_string(), "-v".to_string()]);
+        }
+        args.extend(self.extra_args.iter().cloned());
+        Ok(args)
+    }
+
+    /// Initializes a `Vmm` with the configured options.
+    ///
+    /// # Arguments
+    ///
+    /// * `vmm_path` - Path to the VMM (Virtual Machine Monitor).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `Vmm` instance, `DmaError::InvalidConfig` if the options
+    /// are invalid, or `DmaError::VmmInit` if initialization failed.
+    pub fn build<'a>(&self, vmm_path: &str) -> Result<Vmm<'a>, DmaError> {
+        let args = self.args()?;
+        let args = args.iter().map(String::as_str).collect();
+        Vmm::new(vmm_path, &args).map_err(|e| DmaError::VmmInit(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn args_start_with_the_device() {
+        let args = VmmBuilder::new().device("fpga").args().unwrap();
+        assert_eq!(args, vec!["", "-device", "fpga"]);
+    }
+
+    #[test]
+    fn args_include_every_option_in_order() {
+        let args = VmmBuilder::new()
+            .fpga_device(1)
+            .memmap("mmap.txt")
+            .disable_symbols()
+            .verbose()
+            .arg("-norefresh")
+            .args()
+            .unwrap();
+        let expected = [
+            "",
+            "-device",
+            "fpga://devindex=1",
+            "-memmap",
+            "mmap.txt",
+            "-disable-symbols",
+            "-printf",
+            "-v",
+            "-norefresh",
+        ];
+        assert_eq!(args, expected);
+        let args = VmmBuilder::new().device("fpga").memmap_auto().args().unwrap();
+        assert_eq!(args, vec!["", "-device", "fpga", "-memmap", "auto"]);
+    }
+
+    #[test]
+    fn args_require_a_device() {
+        assert!(matches!(VmmBuilder::new().verbose().args(), Err(DmaError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn args_reject_memmap_with_memmap_auto() {
+        let builder = VmmBuilder::new().device("fpga").memmap("mmap.txt").memmap_auto();
+        assert!(matches!(builder.args(), Err(DmaError::InvalidConfig(_))));
+    }
+}
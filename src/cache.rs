@@ -0,0 +1,305 @@
+use crate::{fix_cr3_value_with_options, try_dtb, Cr3Options, DmaError};
+use memprocfs::{Vmm, VmmProcess};
+use std::collections::HashMap;
+use std::time;
+
+/// Default for how long cached bases are trusted before the PID is checked for reuse.
+const DEFAULT_REUSE_CHECK_INTERVAL: time::Duration = time::Duration::from_secs(1);
+
+/// Cached module bases of a single process.
+#[derive(Debug)]
+struct ProcessEntry {
+    /// EPROCESS address identifying the process instance that owns the PID.
+    eprocess: u64,
+    /// When `eprocess` was last compared with the process now owning the PID.
+    checked_at: time::Instant,
+    /// Lowercase module names and their bases; processes load few enough modules that
+    /// a linear search beats hashing a lowercased copy of the name.
+    bases: Vec<(String, u64)>,
+}
+
+impl ProcessEntry {
+    fn find(&self, module_name: &str) -> Option<u64> {
+        self.bases
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(module_name))
+            .map(|(_, base)| *base)
+    }
+}
+
+/// Memoizes module base addresses keyed by `(pid, module_name)`.
+///
+/// Cache hits do not call into memprocfs or allocate. To notice a PID being reused by
+/// a different process, its EPROCESS address is compared with the cached one on every
+/// miss and on the first hit after the reuse check interval (one second by default),
+/// and the cached bases of the PID are dropped when it changed. Within the interval a
+/// restarted process can therefore still see the old bases; call
+/// [`invalidate`](ModuleCache::invalidate) if a restart is known.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut cache = ModuleCache::new();
+/// loop {
+///     let client = cache.base(&process, "client.dll").expect("Module not found");
+///     // ...
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ModuleCache {
+    processes: HashMap<u32, ProcessEntry>,
+    reuse_check_interval: time::Duration,
+}
+
+impl Default for ModuleCache {
+    fn default() -> Self {
+        Self {
+            processes: HashMap::new(),
+            reuse_check_interval: DEFAULT_REUSE_CHECK_INTERVAL,
+        }
+    }
+}
+
+impl ModuleCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how long cached bases are returned before the PID is checked for reuse again.
+    pub fn with_reuse_check_interval(mut self, interval: time::Duration) -> Self {
+        self.reuse_check_interval = interval;
+        self
+    }
+
+    /// Returns the base address of a module, resolving and caching it on first use.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - Reference to a `VmmProcess` instance representing the target process.
+    /// * `module_name` - Name of the module; matched case-insensitively.
+    ///
+    /// # Returns
+    ///
+    /// An `Option<u64>` containing the base address, or `None` if the module was not found.
+    pub fn base(&mut self, process: &VmmProcess, module_name: &str) -> Option<u64> {
+        self.lookup(
+            process.pid,
+            module_name,
+            time::Instant::now(),
+            || process.info().ok().map(|info| info.va_eprocess),
+            || process.get_module_base(module_name).ok(),
+        )
+    }
+
+    /// Implements [`base`](ModuleCache::base) with the EPROCESS lookup and module
+    /// resolution supplied by the caller.
+    fn lookup(
+        &mut self,
+        pid: u32,
+        module_name: &str,
+        now: time::Instant,
+        eprocess: impl FnOnce() -> Option<u64>,
+        resolve: impl FnOnce() -> Option<u64>,
+    ) -> Option<u64> {
+        if let Some(entry) = self.processes.get(&pid) {
+            if now.saturating_duration_since(entry.checked_at) < self.reuse_check_interval {
+                if let Some(base) = entry.find(module_name) {
+                    return Some(base);
+                }
+            }
+        }
+
+        let eprocess = eprocess()?;
+        let entry = self.processes.entry(pid).or_insert_with(|| ProcessEntry {
+            eprocess,
+            checked_at: now,
+            bases: Vec::new(),
+        });
+        if entry.eprocess != eprocess {
+            entry.eprocess = eprocess;
+            entry.bases.clear();
+        }
+        entry.checked_at = now;
+
+        if let Some(base) = entry.find(module_name) {
+            return Some(base);
+        }
+        let base = resolve()?;
+        entry.bases.push((module_name.to_lowercase(), base));
+        Some(base)
+    }
+
+    /// Removes all cached bases of a process, e.g. after it restarted.
+    pub fn invalidate(&mut self, pid: u32) {
+        self.processes.remove(&pid);
+    }
+
+    /// Removes all cached entries.
+    pub fn clear(&mut self) {
+        self.processes.clear();
+    }
+}
+
+/// Remembers the working DTB of each process so CR3 can be fixed without a full scan.
+///
+/// [`fix_cr3`](DtbCache::fix_cr3) first re-applies the cached DTB and verifies it with a
+/// single module lookup, and only falls back to the full procinfo scan of
+/// [`fix_cr3_with_options`](crate::fix_cr3_with_options) if that fails.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut dtbs = DtbCache::new();
+/// let fixed = dtbs.fix_cr3(&vmm, &process, "game.exe", pid, &Cr3Options::default())?;
+/// ```
+#[derive(Debug, Default)]
+pub struct DtbCache {
+    dtbs: HashMap<u32, u64>,
+}
+
+impl DtbCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fixes the CR3 register of a process, reusing its cached DTB when it still works.
+    ///
+    /// # Arguments
+    ///
+    /// * `vmm` - Reference to a `Vmm` instance.
+    /// * `process` - Reference to a `VmmProcess` instance representing the target process.
+    /// * `target_module` - Name of a module that must resolve with the correct DTB.
+    /// * `pid` - PID of the process.
+    /// * `options` - Poll interval and timeout for the full scan.
+    ///
+    /// # Returns
+    ///
+    /// A `Result<bool, DmaError>` indicating success (`true`) or failure (`false`), with the
+    /// same errors as [`fix_cr3_with_options`](crate::fix_cr3_with_options).
+    pub fn fix_cr3(
+        &mut self,
+        vmm: &Vmm,
+        process: &VmmProcess,
+        target_module: &str,
+        pid: u32,
+        options: &Cr3Options,
+    ) -> Result<bool, DmaError> {
+        if let Some(&dtb) = self.dtbs.get(&pid) {
+            if try_dtb(vmm, target_module, pid, dtb) {
+                return Ok(true);
+            }
+            log::debug!("Cached DTB 0x{:X} for PID {} no longer works", dtb, pid);
+            self.dtbs.remove(&pid);
+        }
+
+        match fix_cr3_value_with_options(vmm, process, target_module, pid, options)? {
+            Some(dtb) => {
+                self.dtbs.insert(pid, dtb);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Returns the cached DTB of a process, if any.
+    pub fn get(&self, pid: u32) -> Option<u64> {
+        self.dtbs.get(&pid).copied()
+    }
+
+    /// Stores a known working DTB for a process, e.g. one persisted from an earlier session.
+    pub fn insert(&mut self, pid: u32, dtb: u64) {
+        self.dtbs.insert(pid, dtb);
+    }
+
+    /// Removes the cached DTB of a process.
+    pub fn invalidate(&mut self, pid: u32) {
+        self.dtbs.remove(&pid);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PID: u32 = 1234;
+    const SECOND: time::Duration = time::Duration::from_secs(1);
+
+    #[test]
+    fn module_cache_serves_hits_without_lookups() {
+        let mut cache = ModuleCache::new();
+        let start = time::Instant::now();
+        assert_eq!(
+            cache.lookup(
+                PID,
+                "client.dll",
+                start,
+                || Some(0xA000),
+                || Some(0x7FF0_0000)
+            ),
+            Some(0x7FF0_0000)
+        );
+        let hit = cache.lookup(
+            PID,
+            "CLIENT.DLL",
+            start,
+            || panic!("EPROCESS checked on a hit"),
+            || panic!("module resolved on a hit"),
+        );
+        assert_eq!(hit, Some(0x7FF0_0000));
+    }
+
+    #[test]
+    fn module_cache_drops_bases_when_the_pid_is_reused() {
+        let mut cache = ModuleCache::new();
+        let start = time::Instant::now();
+        cache.lookup(
+            PID,
+            "client.dll",
+            start,
+            || Some(0xA000),
+            || Some(0x7FF0_0000),
+        );
+
+        // Still trusted within the interval, then rechecked and found to be a new process.
+        let later = start + SECOND / 2;
+        assert_eq!(
+            cache.lookup(PID, "client.dll", later, || Some(0xB000), || None),
+            Some(0x7FF0_0000)
+        );
+        let after_interval = start + SECOND * 2;
+        assert_eq!(
+            cache.lookup(
+                PID,
+                "client.dll",
+                after_interval,
+                || Some(0xB000),
+                || Some(0x7FE0_0000)
+            ),
+            Some(0x7FE0_0000)
+        );
+    }
+
+    #[test]
+    fn module_cache_keeps_bases_when_the_process_is_unchanged() {
+        let mut cache = ModuleCache::new();
+        let start = time::Instant::now();
+        cache.lookup(
+            PID,
+            "client.dll",
+            start,
+            || Some(0xA000),
+            || Some(0x7FF0_0000),
+        );
+        let after_interval = start + SECOND * 2;
+        let base = cache.lookup(
+            PID,
+            "client.dll",
+            after_interval,
+            || Some(0xA000),
+            || panic!("module resolved again"),
+        );
+        assert_eq!(base, Some(0x7FF0_0000));
+    }
+}
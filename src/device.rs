@@ -0,0 +1,245 @@
+use crate::{read_bytes_ex, DmaError, ReadFlags};
+use memprocfs::{LeechCore, Vmm};
+use std::time;
+
+/// Number of bytes read per iteration by [`benchmark_read`].
+const BENCHMARK_READ_SIZE: usize = 0x1000;
+
+/// Information about the memory acquisition device, as reported by LeechCore.
+///
+/// Fields are `None` when the device does not report them; the FPGA fields are only
+/// available for FPGA devices. LeechCore does not expose the negotiated PCIe link width
+/// and speed, so check those in the verbose output of memprocfs (`-v`) instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceInfo {
+    /// LeechCore version as `(major, minor, revision)`.
+    pub leechcore_version: Option<(u64, u64, u64)>,
+    /// Highest physical address that can be read.
+    pub max_address: Option<u64>,
+    /// FPGA bitstream ID, identifying the board type.
+    pub fpga_id: Option<u64>,
+    /// FPGA firmware version as `(major, minor)`.
+    pub fpga_version: Option<(u64, u64)>,
+    /// PCIe bus, device and function of the FPGA, e.g. `0x0400` for `04:00.0`.
+    pub pcie_device_id: Option<u64>,
+}
+
+impl DeviceInfo {
+    /// Returns whether the device is an FPGA.
+    pub fn is_fpga(&self) -> bool {
+        self.fpga_id.is_some()
+    }
+}
+
+/// Retrieves information about the memory acquisition device.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+///
+/// # Returns
+///
+/// A `Result` containing the `DeviceInfo`, or `DmaError::InvalidConfig` if the device
+/// reports nothing at all.
+///
+/// # Examples
+///
+/// ```ignore
+/// let device = get_device_info(&vmm)?;
+/// if let Some((major, minor)) = device.fpga_version {
+///     println!("FPGA firmware v{}.{}", major, minor);
+/// }
+/// ```
+pub fn get_device_info(vmm: &Vmm) -> Result<DeviceInfo, DmaError> {
+    let option = |id| vmm.get_config(id).ok();
+    let version = |major, minor| Some((option(major)?, option(minor)?));
+    let info = DeviceInfo {
+        leechcore_version: version(LeechCore::LC_OPT_CORE_VERSION_MAJOR, LeechCore::LC_OPT_CORE_VERSION_MINOR)
+            .and_then(|(major, minor)| Some((major, minor, option(LeechCore::LC_OPT_CORE_VERSION_REVISION)?))),
+        max_address: option(LeechCore::LC_OPT_CORE_ADDR_MAX),
+        fpga_id: option(LeechCore::LC_OPT_FPGA_FPGA_ID),
+        fpga_version: version(LeechCore::LC_OPT_FPGA_VERSION_MAJOR, LeechCore::LC_OPT_FPGA_VERSION_MINOR),
+        pcie_device_id: option(LeechCore::LC_OPT_FPGA_DEVICE_ID),
+    };
+    if info.leechcore_version.is_none() && info.max_address.is_none() && !info.is_fpga() {
+        return Err(DmaError::InvalidConfig("device did not report any information".to_string()));
+    }
+    Ok(info)
+}
+
+/// Returns the LeechCore device string selecting the FPGA device at `index`.
+pub(crate) fn fpga_device_config(index: u32) -> String {
+    format!("fpga://devindex={}", index)
+}
+
+/// An FPGA device found by [`list_fpga_devices`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FpgaDevice {
+    /// Index to pass to [`VmmBuilder::fpga_device`](crate::VmmBuilder::fpga_device).
+    pub index: u32,
+    /// FPGA bitstream ID, identifying the board type.
+    pub fpga_id: Option<u64>,
+    /// FPGA firmware version as `(major, minor)`.
+    pub fpga_version: Option<(u64, u64)>,
+    /// PCIe bus, device and function of the FPGA, e.g. `0x0400` for `04:00.0`.
+    pub pcie_device_id: Option<u64>,
+}
+
+/// Enumerates the FPGA devices connected to this computer.
+///
+/// Every index from `0` to `max_devices - 1` is opened with LeechCore and closed again
+/// right away, so the PCIe device ID of each card can be matched to its target. A
+/// device that is already open, e.g. by another `Vmm`, cannot be opened again and is
+/// missing from the result; call this before initializing any `Vmm`.
+///
+/// # Arguments
+///
+/// * `leechcore_path` - Path to the LeechCore library, e.g. `leechcore.dll` next to `vmm.dll`.
+/// * `max_devices` - Number of device indices to try.
+///
+/// # Returns
+///
+/// A `Vec<FpgaDevice>` with one entry per device that could be opened.
+///
+/// # Examples
+///
+/// ```ignore
+/// for device in list_fpga_devices("leechcore.dll", 4) {
+///     println!("#{}: PCIe {:04X?}", device.index, device.pcie_device_id);
+/// }
+/// ```
+pub fn list_fpga_devices(leechcore_path: &str, max_devices: u32) -> Vec<FpgaDevice> {
+    (0..max_devices)
+        .filter_map(|index| {
+            let device = fpga_device_config(index);
+            let leechcore = match LeechCore::new(leechcore_path, &device, LeechCore::LC_CONFIG_PRINTF_NONE) {
+                Ok(leechcore) => leechcore,
+                Err(e) => {
+                    log::debug!("Failed to open {}: {}", device, e);
+                    return None;
+                }
+            };
+            let option = |id| leechcore.get_option(id).ok();
+            Some(FpgaDevice {
+                index,
+                fpga_id: option(LeechCore::LC_OPT_FPGA_FPGA_ID),
+                fpga_version: option(LeechCore::LC_OPT_FPGA_VERSION_MAJOR)
+                    .zip(option(LeechCore::LC_OPT_FPGA_VERSION_MINOR)),
+                pcie_device_id: option(LeechCore::LC_OPT_FPGA_DEVICE_ID),
+            })
+        })
+        .collect()
+}
+
+/// Closes a `Vmm` and releases its device handle.
+///
+/// Dropping a `Vmm` already calls `VMMDLL_Close`, so this only makes the release point
+/// explicit, e.g. between attach/detach cycles. Note that `Vmm::clone` opens another
+/// handle to the same device, and the device stays open until every clone is dropped.
+/// A `Vmm` created from an existing native handle does not own it and leaves it open.
+///
+/// # Arguments
+///
+/// * `vmm` - The `Vmm` instance to close.
+///
+/// # Examples
+///
+/// ```ignore
+/// let vmm = VmmBuilder::new().device("fpga").build("vmm.dll")?;
+/// // ...
+/// close(vmm);
+/// ```
+pub fn close(vmm: Vmm) {
+    drop(vmm);
+}
+
+/// Result of a [`benchmark_read`] run.
+#[derive(Debug, Clone, Copy)]
+pub struct ReadBenchmark {
+    /// Number of reads attempted.
+    pub iterations: usize,
+    /// Number of reads that failed.
+    pub failures: usize,
+    /// Total number of bytes read successfully.
+    pub bytes: u64,
+    /// Time taken by all reads.
+    pub elapsed: time::Duration,
+}
+
+impl ReadBenchmark {
+    /// Throughput of the successful reads in megabytes per second.
+    pub fn mb_per_sec(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs == 0.0 {
+            return 0.0;
+        }
+        self.bytes as f64 / (1024.0 * 1024.0) / secs
+    }
+
+    /// Average time per read.
+    pub fn average_latency(&self) -> time::Duration {
+        self.elapsed / self.iterations.max(1) as u32
+    }
+}
+
+/// Times `iterations` uncached 4 KiB reads at an address to measure device throughput.
+///
+/// Reads bypass the memprocfs cache, so the result reflects the DMA link rather than
+/// memory on the host. Use a readable address, such as a module base.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `pid` - PID of the process to read from.
+/// * `addr` - Virtual address to read from.
+/// * `iterations` - Number of reads to perform.
+///
+/// # Returns
+///
+/// A `Result` containing the `ReadBenchmark`, or `DmaError::ProcessNotFound` if the
+/// process does not exist.
+///
+/// # Examples
+///
+/// ```ignore
+/// let result = benchmark_read(&vmm, pid, base_address, 1000)?;
+/// println!("{:.1} MB/s, {:?} per read", result.mb_per_sec(), result.average_latency());
+/// ```
+pub fn benchmark_read(vmm: &Vmm, pid: u32, addr: u64, iterations: usize) -> Result<ReadBenchmark, DmaError> {
+    let process = vmm
+        .process_from_pid(pid)
+        .map_err(|_| DmaError::ProcessNotFound(format!("with PID {}", pid)))?;
+    let mut failures = 0;
+    let start = time::Instant::now();
+    for _ in 0..iterations {
+        if read_bytes_ex(&process, addr, BENCHMARK_READ_SIZE, ReadFlags::no_cache()).is_err() {
+            failures += 1;
+        }
+    }
+    Ok(ReadBenchmark {
+        iterations,
+        failures,
+        bytes: ((iterations - failures) * BENCHMARK_READ_SIZE) as u64,
+        elapsed: start.elapsed(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    /// Opens and closes the device named by `DMALIB_DEVICE` (default `fpga`) with the
+    /// memprocfs library at `DMALIB_VMM_PATH`; a leaked handle makes a later open fail.
+    #[test]
+    #[ignore = "requires a memory acquisition device"]
+    fn open_and_close_repeatedly() {
+        let vmm_path = env::var("DMALIB_VMM_PATH").expect("DMALIB_VMM_PATH is not set");
+        let device = env::var("DMALIB_DEVICE").unwrap_or_else(|_| "fpga".to_string());
+        for cycle in 0..50 {
+            let vmm = Vmm::new(&vmm_path, &["", "-device", &device, "-norefresh"].to_vec())
+                .unwrap_or_else(|e| panic!("open {} failed: {}", cycle, e));
+            close(vmm);
+        }
+    }
+}
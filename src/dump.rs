@@ -0,0 +1,141 @@
+use crate::module::find_module;
+use crate::{pe, DmaError, ScanOptions};
+use memprocfs::{VmmProcess, FLAG_ZEROPAD_ON_FAIL};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Dumps the in-memory image of a module to a file.
+///
+/// The image is read in chunks with memprocfs' zero-padding enabled, so pages that are
+/// not mapped or paged out are written as zeros instead of failing the dump. The file
+/// has the memory layout of the image; use [`dump_module_pe`] for a file whose headers
+/// match that layout.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `module_name` - Name of the module to dump.
+/// * `out_path` - Path of the file to write.
+///
+/// # Returns
+///
+/// A `Result` which is `Ok(())` once the file is written, `DmaError::ModuleNotFound` if
+/// the module is not loaded, or `DmaError::Io` if the file could not be written.
+///
+/// # Examples
+///
+/// ```ignore
+/// dump_module(&process, "game.exe", Path::new("game.exe.dump"))?;
+/// ```
+pub fn dump_module(process: &VmmProcess, module_name: &str, out_path: &Path) -> Result<(), DmaError> {
+    dump_module_with_options(process, module_name, out_path, &ScanOptions::default())
+}
+
+/// Dumps the in-memory image of a module to a file, with a configurable chunk size.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `module_name` - Name of the module to dump.
+/// * `out_path` - Path of the file to write.
+/// * `options` - Chunk size to read with.
+///
+/// # Returns
+///
+/// A `Result` which is `Ok(())` once the file is written; see [`dump_module`].
+///
+/// # Examples
+///
+/// ```ignore
+/// let options = ScanOptions { chunk_size: 0x100000 };
+/// dump_module_with_options(&process, "game.exe", Path::new("game.exe.dump"), &options)?;
+/// ```
+pub fn dump_module_with_options(process: &VmmProcess, module_name: &str, out_path: &Path, options: &ScanOptions) -> Result<(), DmaError> {
+    let module = find_module(process, module_name)?;
+    let image = read_image(process, module.base, module.size as usize, options);
+    write_file(out_path, &image)
+}
+
+/// Dumps a module to a PE file that disassemblers can open.
+///
+/// The image is read like [`dump_module`], and the section headers are then rewritten
+/// so every section's raw data lives at its virtual address in the file
+/// (`PointerToRawData = VirtualAddress`, `SizeOfRawData` spanning to the next section).
+/// Imports are left as found in memory, so the IAT holds the resolved function
+/// addresses of the dumped process.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `module_name` - Name of the module to dump.
+/// * `out_path` - Path of the file to write.
+///
+/// # Returns
+///
+/// A `Result` which is `Ok(())` once the file is written, `DmaError::ModuleNotFound` if
+/// the module is not loaded, `DmaError::InvalidPe` if the headers in memory could not be
+/// parsed, or `DmaError::Io` if the file could not be written.
+///
+/// # Examples
+///
+/// ```ignore
+/// dump_module_pe(&process, "game.exe", Path::new("game_dump.exe"))?;
+/// ```
+pub fn dump_module_pe(process: &VmmProcess, module_name: &str, out_path: &Path) -> Result<(), DmaError> {
+    dump_module_pe_with_options(process, module_name, out_path, &ScanOptions::default())
+}
+
+/// Dumps a module to a PE file that disassemblers can open, with a configurable chunk
+/// size.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `module_name` - Name of the module to dump.
+/// * `out_path` - Path of the file to write.
+/// * `options` - Chunk size to read with.
+///
+/// # Returns
+///
+/// A `Result` which is `Ok(())` once the file is written; see [`dump_module_pe`].
+///
+/// # Examples
+///
+/// ```ignore
+/// let options = ScanOptions { chunk_size: 0x100000 };
+/// dump_module_pe_with_options(&process, "game.exe", Path::new("game_dump.exe"), &options)?;
+/// ```
+pub fn dump_module_pe_with_options(process: &VmmProcess, module_name: &str, out_path: &Path, options: &ScanOptions) -> Result<(), DmaError> {
+    let module = find_module(process, module_name)?;
+    let mut image = read_image(process, module.base, module.size as usize, options);
+    pe::fix_raw_layout(&mut image)?;
+    write_file(out_path, &image)
+}
+
+/// Reads `size` bytes of an image, zero-filling anything that cannot be read.
+pub(crate) fn read_image(process: &VmmProcess, base: u64, size: usize, options: &ScanOptions) -> Vec<u8> {
+    let mut image = Vec::with_capacity(size);
+    while image.len() < size {
+        let addr = base + image.len() as u64;
+        let len = (options.step() as usize).min(size - image.len());
+        match process.mem_read_ex(addr, len, FLAG_ZEROPAD_ON_FAIL) {
+            Ok(chunk) if chunk.len() == len => image.extend_from_slice(&chunk),
+            _ => {
+                log::debug!("Zero-filling {} unreadable bytes at 0x{:X}", len, addr);
+                image.resize(image.len() + len, 0);
+            }
+        }
+    }
+    image
+}
+
+/// Writes `bytes` to a new file at `path`, replacing any existing file.
+pub(crate) fn write_file(path: &Path, bytes: &[u8]) -> Result<(), DmaError> {
+    let file = File::create(path).map_err(|e| DmaError::Io(format!("{}: {}", path.display(), e)))?;
+    let mut writer = BufWriter::new(file);
+    writer
+        .write_all(bytes)
+        .and_then(|_| writer.flush())
+        .map_err(|e| DmaError::Io(format!("{}: {}", path.display(), e)))
+}
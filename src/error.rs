@@ -0,0 +1,111 @@
+use std::error::Error;
+use std::fmt;
+
+/// Errors returned by this crate.
+///
+/// # Examples
+///
+/// ```ignore
+/// match read::<u32>(&vmm, pid, addr) {
+///     Ok(value) => println!("Value: {}", value),
+///     Err(DmaError::ProcessNotFound(_)) => retry(),
+///     Err(e) => println!("Read failed: {}", e),
+/// }
+/// ```
+#[derive(Debug)]
+pub enum DmaError {
+    /// The `Vmm` could not be initialized.
+    VmmInit(String),
+    /// The initialization options are invalid.
+    InvalidConfig(String),
+    /// An argument is outside the range the function supports.
+    InvalidArgument(String),
+    /// The `Vmm` is not connected, e.g. after a failed reconnect.
+    Disconnected,
+    /// No process matched the given name or PID.
+    ProcessNotFound(String),
+    /// Information about a process could not be retrieved.
+    ProcessInfo(String),
+    /// No thread with the given thread ID exists in the process.
+    ThreadNotFound(u32),
+    /// No module matched the given name.
+    ModuleNotFound(String),
+    /// The module does not export the given symbol.
+    ExportNotFound { module: String, export: String },
+    /// The module has no section with the given name.
+    SectionNotFound { module: String, section: String },
+    /// Fewer than `len` bytes could be read at `addr`.
+    Read { addr: u64, len: usize },
+    /// The `len` bytes at `addr` could not be written.
+    Write { addr: u64, len: usize },
+    /// No working DTB could be found for the process.
+    Cr3Resolution,
+    /// A virtual file system access failed.
+    Vfs(String),
+    /// A signature pattern could not be parsed.
+    InvalidPattern(String),
+    /// The signature pattern did not match anywhere in the module.
+    PatternNotFound { module: String, pattern: String },
+    /// A string structure in target memory is malformed.
+    InvalidString(String),
+    /// A scatter operation failed.
+    Scatter(String),
+    /// The pointer read at `addr` was null.
+    NullPointer { addr: u64 },
+    /// A read at `offset` was attempted from a null base address, usually because a
+    /// module or object lookup failed.
+    NullBase { offset: u64 },
+    /// The virtual address `addr` is not mapped or is paged out.
+    Unmapped { addr: u64 },
+    /// A memprocfs info map could not be retrieved.
+    Map(String),
+    /// A debug symbol or type could not be resolved.
+    Symbol(String),
+    /// A PE image in target memory could not be parsed.
+    InvalidPe(String),
+    /// An operation did not complete within its timeout.
+    Timeout,
+    /// A local file could not be read or written.
+    Io(String),
+    /// A local file's contents could not be parsed.
+    Parse(String),
+    /// The operation is not available on this target or with this memprocfs setup.
+    Unsupported(String),
+}
+
+impl fmt::Display for DmaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DmaError::VmmInit(e) => write!(f, "Failed to initialize Vmm: {}", e),
+            DmaError::InvalidConfig(e) => write!(f, "Invalid Vmm configuration: {}", e),
+            DmaError::InvalidArgument(e) => write!(f, "Invalid argument: {}", e),
+            DmaError::Disconnected => write!(f, "Vmm is not connected"),
+            DmaError::ProcessNotFound(process) => write!(f, "Failed to find process {}", process),
+            DmaError::ProcessInfo(e) => write!(f, "Failed to get process information: {}", e),
+            DmaError::ThreadNotFound(tid) => write!(f, "Failed to find thread {}", tid),
+            DmaError::ModuleNotFound(module) => write!(f, "Failed to find module {}", module),
+            DmaError::ExportNotFound { module, export } => write!(f, "Failed to find export {}!{}", module, export),
+            DmaError::SectionNotFound { module, section } => write!(f, "Failed to find section {} in {}", section, module),
+            DmaError::Read { addr, len } => write!(f, "Failed to read {} bytes at 0x{:X}", len, addr),
+            DmaError::Write { addr, len } => write!(f, "Failed to write {} bytes at 0x{:X}", len, addr),
+            DmaError::Cr3Resolution => write!(f, "Failed to resolve CR3"),
+            DmaError::Vfs(e) => write!(f, "Failed to access VFS: {}", e),
+            DmaError::InvalidPattern(e) => write!(f, "Invalid pattern: {}", e),
+            DmaError::PatternNotFound { module, pattern } => write!(f, "Failed to find pattern {} in {}", pattern, module),
+            DmaError::InvalidString(e) => write!(f, "Invalid string: {}", e),
+            DmaError::Scatter(e) => write!(f, "Scatter operation failed: {}", e),
+            DmaError::NullPointer { addr } => write!(f, "Null pointer read at 0x{:X}", addr),
+            DmaError::NullBase { offset } => write!(f, "Read at offset 0x{:X} from a null base address", offset),
+            DmaError::Unmapped { addr } => write!(f, "Address 0x{:X} is not mapped", addr),
+            DmaError::Map(e) => write!(f, "Failed to retrieve map: {}", e),
+            DmaError::Symbol(e) => write!(f, "Failed to resolve symbol {}", e),
+            DmaError::InvalidPe(e) => write!(f, "Invalid PE image: {}", e),
+            DmaError::Timeout => write!(f, "Operation timed out"),
+            DmaError::Io(e) => write!(f, "Failed to access file: {}", e),
+            DmaError::Parse(e) => write!(f, "Failed to parse {}", e),
+            DmaError::Unsupported(e) => write!(f, "Unsupported operation: {}", e),
+        }
+    }
+}
+
+impl Error for DmaError {}
@@ -0,0 +1,254 @@
+use crate::module::find_module;
+use crate::{list_exports, list_imports, list_modules, pe, read_bytes, read_process, DmaError, ModuleInfo, ScatterReader};
+use memprocfs::VmmProcess;
+
+/// Number of bytes read from the start of each export to look for a jump.
+const PROLOGUE_LEN: usize = 16;
+
+/// An IAT slot whose function pointer differs from the export it was bound to.
+#[derive(Debug, Clone)]
+pub struct IatHook {
+    /// Name of the imported function.
+    pub name: String,
+    /// Name of the module the function is imported from.
+    pub module: String,
+    /// Address of the IAT slot.
+    pub iat_slot: u64,
+    /// Address the function is exported at.
+    pub expected: u64,
+    /// Address currently stored in the IAT slot.
+    pub actual: u64,
+    /// Name of the loaded module containing `actual`, or `None` if it points outside
+    /// every module, e.g. into injected code.
+    pub target_module: Option<String>,
+}
+
+/// Detects IAT hooks in a module by comparing its IAT slots to the real exports.
+///
+/// Every import by name is resolved in its source module, and slots holding a
+/// different address are reported. Imports that cannot be resolved, such as ordinal
+/// imports or imports through API set names, are skipped. Exports that the loader
+/// resolves by forwarding to another module also show up as mismatches, but their
+/// `target_module` is a loaded module, while hooks into injected code have none.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `module_name` - Name of the importing module to check.
+///
+/// # Returns
+///
+/// A `Result` containing a `Vec<IatHook>` for every mismatching slot, or an error if
+/// the module or its imports could not be read; see [`list_imports`].
+///
+/// # Examples
+///
+/// ```ignore
+/// for hook in detect_iat_hooks(&process, "game.exe")? {
+///     println!("{}!{}: 0x{:X} -> 0x{:X} ({:?})", hook.module, hook.name, hook.expected, hook.actual, hook.target_module);
+/// }
+/// ```
+pub fn detect_iat_hooks(process: &VmmProcess, module_name: &str) -> Result<Vec<IatHook>, DmaError> {
+    let modules = list_modules(process)?;
+    let mut hooks = Vec::new();
+
+    for import in list_imports(process, module_name)? {
+        if import.name.starts_with('#') {
+            continue;
+        }
+        let Ok(expected) = process.get_proc_address(&import.module, &import.name) else {
+            continue;
+        };
+        if expected == import.address {
+            continue;
+        }
+        hooks.push(IatHook {
+            target_module: module_containing(&modules, import.address).map(|module| module.name.clone()),
+            name: import.name,
+            module: import.module,
+            iat_slot: import.iat_slot,
+            expected,
+            actual: import.address,
+        });
+    }
+
+    Ok(hooks)
+}
+
+/// Checks whether the first bytes of a function differ from the expected prologue.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Address of the function.
+/// * `expected_bytes` - Unpatched prologue bytes, e.g. taken from the file on disk.
+///
+/// # Returns
+///
+/// A `Result` containing `true` if the bytes differ, or `DmaError::Read` if they could
+/// not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let hooked = check_inline_hook(&process, create_file, &[0x4C, 0x8B, 0xD1, 0xB8])?;
+/// ```
+pub fn check_inline_hook(process: &VmmProcess, addr: u64, expected_bytes: &[u8]) -> Result<bool, DmaError> {
+    Ok(read_bytes(process, addr, expected_bytes.len())? != expected_bytes)
+}
+
+/// An exported function whose first instruction jumps out of its module.
+#[derive(Debug, Clone)]
+pub struct InlineHook {
+    /// Name of the export.
+    pub name: String,
+    /// Address of the export.
+    pub address: u64,
+    /// Address the patched prologue jumps to.
+    pub target: u64,
+    /// Name of the loaded module containing `target`, or `None` if it points outside
+    /// every module, e.g. into injected code.
+    pub target_module: Option<String>,
+}
+
+/// Scans the exported functions of a module for prologues patched with a jump.
+///
+/// The first bytes of every export are read in one scatter batch and checked for a
+/// `jmp rel32` (`E9`), `jmp [mem]` (`FF 25`) or `mov rax, imm64; jmp rax`
+/// (`48 B8 .. FF E0`) whose target lies outside the module. Some system DLLs contain
+/// legitimate stubs that jump into another module, so hooks whose `target_module` is
+/// `None` are the most suspicious.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `module_name` - Name of the module whose exports are checked.
+///
+/// # Returns
+///
+/// A `Result` containing a `Vec<InlineHook>` for every hooked export,
+/// `DmaError::ModuleNotFound` if the module is not loaded, or another error if its
+/// headers or exports could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// for hook in scan_inline_hooks(&process, "ntdll.dll")? {
+///     println!("{} -> 0x{:X} ({:?})", hook.name, hook.target, hook.target_module);
+/// }
+/// ```
+pub fn scan_inline_hooks(process: &VmmProcess, module_name: &str) -> Result<Vec<InlineHook>, DmaError> {
+    let modules = list_modules(process)?;
+    let module = &find_module(process, module_name)?;
+    let is_64 = pe::read_headers(process, module.base)?.is_64;
+    let exports: Vec<_> = list_exports(process, module_name)?
+        .into_iter()
+        .filter(|export| export.forwarded_to.is_none() && contains(module, export.address))
+        .collect();
+    if exports.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let scatter = ScatterReader::new(process)?;
+    for export in &exports {
+        scatter.prepare(export.address, PROLOGUE_LEN)?;
+    }
+    scatter.execute()?;
+
+    let mut hooks = Vec::new();
+    for export in exports {
+        let Ok(prologue) = scatter.read_bytes(export.address, PROLOGUE_LEN) else {
+            continue;
+        };
+        let target = match decode_jump(&prologue, export.address, is_64) {
+            Some(Jump::Direct(target)) => target,
+            Some(Jump::Indirect(slot)) if is_64 => match read_process::<u64>(process, slot) {
+                Ok(target) => target,
+                Err(_) => continue,
+            },
+            Some(Jump::Indirect(slot)) => match read_process::<u32>(process, slot) {
+                Ok(target) => target as u64,
+                Err(_) => continue,
+            },
+            None => continue,
+        };
+        if contains(module, target) {
+            continue;
+        }
+        hooks.push(InlineHook {
+            name: export.name,
+            address: export.address,
+            target,
+            target_module: module_containing(&modules, target).map(|module| module.name.clone()),
+        });
+    }
+
+    Ok(hooks)
+}
+
+/// Destination of a jump instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Jump {
+    /// The jump goes to this address.
+    Direct(u64),
+    /// The jump goes to the address stored at this address.
+    Indirect(u64),
+}
+
+/// Decodes a jump at the start of `bytes`, which were read from `addr`.
+fn decode_jump(bytes: &[u8], addr: u64, is_64: bool) -> Option<Jump> {
+    let i32_at = |offset: usize| pe::u32_at(bytes, offset).map(|value| value as i32 as i64);
+    match bytes {
+        [0xE9, ..] => Some(Jump::Direct((addr + 5).wrapping_add_signed(i32_at(1)?))),
+        [0xFF, 0x25, ..] if is_64 => Some(Jump::Indirect((addr + 6).wrapping_add_signed(i32_at(2)?))),
+        [0xFF, 0x25, ..] => Some(Jump::Indirect(pe::u32_at(bytes, 2)? as u64)),
+        [0x48, 0xB8, _, _, _, _, _, _, _, _, 0xFF, 0xE0, ..] => Some(Jump::Direct(pe::u64_at(bytes, 2)?)),
+        _ => None,
+    }
+}
+
+/// Whether `addr` lies within the image of `module`.
+fn contains(module: &ModuleInfo, addr: u64) -> bool {
+    addr >= module.base && addr - module.base < module.size
+}
+
+/// Returns the module whose image contains `addr`.
+fn module_containing(modules: &[ModuleInfo], addr: u64) -> Option<&ModuleInfo> {
+    modules.iter().find(|module| contains(module, addr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_jump_resolves_rel32_jumps() {
+        let forward = [0xE9, 0x10, 0x00, 0x00, 0x00];
+        assert_eq!(decode_jump(&forward, 0x7FF8_0000_1000, true), Some(Jump::Direct(0x7FF8_0000_1015)));
+        // jmp -0x20
+        let backward = [0xE9, 0xE0, 0xFF, 0xFF, 0xFF];
+        assert_eq!(decode_jump(&backward, 0x7FF8_0000_1000, true), Some(Jump::Direct(0x7FF8_0000_0FE5)));
+    }
+
+    #[test]
+    fn decode_jump_resolves_indirect_jumps_by_bitness() {
+        let bytes = [0xFF, 0x25, 0xFA, 0xFF, 0xFF, 0xFF];
+        // x64: RIP-relative slot, here 6 bytes before the end of the instruction.
+        assert_eq!(decode_jump(&bytes, 0x1000, true), Some(Jump::Indirect(0x1000)));
+        // x86: absolute slot address.
+        assert_eq!(decode_jump(&bytes, 0x1000, false), Some(Jump::Indirect(0xFFFF_FFFA)));
+    }
+
+    #[test]
+    fn decode_jump_resolves_mov_rax_jmp_rax() {
+        let bytes = [0x48, 0xB8, 0x00, 0x20, 0x34, 0x12, 0xF8, 0x7F, 0x00, 0x00, 0xFF, 0xE0];
+        assert_eq!(decode_jump(&bytes, 0x1000, true), Some(Jump::Direct(0x7FF8_1234_2000)));
+    }
+
+    #[test]
+    fn decode_jump_ignores_other_instructions() {
+        assert_eq!(decode_jump(&[0x4C, 0x8B, 0xD1, 0xB8, 0x55, 0x00], 0x1000, true), None);
+        assert_eq!(decode_jump(&[0xE9, 0x10], 0x1000, true), None);
+        assert_eq!(decode_jump(&[0x48, 0xB8, 0, 0, 0, 0, 0, 0, 0, 0, 0x90, 0x90], 0x1000, true), None);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{DmaError, PageProtection};
+use memprocfs::VmmProcess;
+
+/// Allocates memory in a target process.
+///
+/// DMA devices can only read and write physical memory that already exists; they
+/// cannot call into the target's memory manager, and memprocfs offers no allocation
+/// API. Creating a VAD and page table entries by hand behind the kernel's back would
+/// corrupt its bookkeeping, so this function always fails. The supported way to place
+/// code in a target is to reuse memory that is already mapped with the required
+/// protection, such as unused padding at the end of an executable section (a code
+/// cave) found with [`get_sections`](crate::get_sections) and
+/// [`query_protection`](crate::query_protection).
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `size` - Number of bytes to allocate.
+/// * `protect` - Protection the memory should have.
+///
+/// # Returns
+///
+/// Always `DmaError::Unsupported`.
+///
+/// # Examples
+///
+/// ```ignore
+/// match alloc(&process, 0x1000, PageProtection { read: true, write: true, execute: true }) {
+///     Err(DmaError::Unsupported(reason)) => println!("{}", reason),
+///     _ => unreachable!(),
+/// }
+/// ```
+pub fn alloc(process: &VmmProcess, size: usize, protect: PageProtection) -> Result<u64, DmaError> {
+    log::debug!(
+        "Refusing to allocate {} bytes ({:?}) in PID {}: DMA cannot allocate memory",
+        size,
+        protect,
+        process.pid
+    );
+    Err(DmaError::Unsupported(
+        "memory cannot be allocated over DMA; reuse existing mapped memory such as a code cave".to_string(),
+    ))
+}
+
+/// How [`execute_shellcode`] would make the target run the code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellcodeTrigger {
+    /// Queue a user-mode APC to an alertable thread.
+    Apc,
+    /// Redirect the saved instruction pointer of a suspended thread.
+    ThreadHijack,
+}
+
+/// Writes shellcode to fresh executable memory in a target and makes it run.
+///
+/// Neither trigger can be delivered over DMA: both need freshly allocated executable
+/// memory, which [`alloc`] cannot provide, and queueing an APC or changing a thread's
+/// context has to go through the target's kernel, which a DMA device cannot call. Edits
+/// to live kernel thread structures from outside race with the scheduler and would
+/// crash the target. This function therefore always fails; running code in the target
+/// requires a component executing on the target itself.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `code` - Position-independent machine code to run.
+/// * `trigger` - Mechanism used to start the code.
+///
+/// # Returns
+///
+/// Always `DmaError::Unsupported`.
+///
+/// # Examples
+///
+/// ```ignore
+/// if let Err(e) = execute_shellcode(&process, &code, ShellcodeTrigger::Apc) {
+///     println!("{}", e);
+/// }
+/// ```
+pub fn execute_shellcode(process: &VmmProcess, code: &[u8], trigger: ShellcodeTrigger) -> Result<(), DmaError> {
+    log::debug!(
+        "Refusing to run {} bytes of shellcode in PID {} via {:?}: DMA cannot start code",
+        code.len(),
+        process.pid,
+        trigger
+    );
+    Err(DmaError::Unsupported(format!(
+        "{:?} cannot be triggered over DMA without allocation or a kernel call",
+        trigger
+    )))
+}
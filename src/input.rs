@@ -0,0 +1,243 @@
+use crate::{read_bytes_ex, read_process, DmaError, ReadFlags};
+use memprocfs::{Vmm, VmmProcess};
+
+/// Bit that makes memprocfs include kernel and session space in a process' address space.
+const PID_WITH_KERNEL_MEMORY: u32 = 0x8000_0000;
+
+/// Size of `gafAsyncKeyState`: two bits for each of the 256 virtual-key codes.
+const KEY_STATE_SIZE: usize = 64;
+
+const VK_LBUTTON: u8 = 0x01;
+const VK_RBUTTON: u8 = 0x02;
+const VK_MBUTTON: u8 = 0x04;
+const VK_XBUTTON1: u8 = 0x05;
+const VK_XBUTTON2: u8 = 0x06;
+
+/// Which mouse buttons are held down on the target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MouseButtons {
+    /// Left button.
+    pub left: bool,
+    /// Right button.
+    pub right: bool,
+    /// Middle button or wheel click.
+    pub middle: bool,
+    /// First extra ("back") button.
+    pub x1: bool,
+    /// Second extra ("forward") button.
+    pub x2: bool,
+}
+
+/// Reads the target's keyboard state from the win32k async key state table.
+///
+/// The table (`win32kbase.sys!gafAsyncKeyState`) lives in session space, so it is
+/// resolved and read through `winlogon.exe` of the interactive session with kernel
+/// memory mapped in. This requires memprocfs to be able to parse the export table of
+/// `win32kbase.sys`, which works on Windows 10. Windows 11 no longer exports the table,
+/// in which case [`Keyboard::new`] returns `DmaError::Unsupported`.
+///
+/// # Examples
+///
+/// ```ignore
+/// let keyboard = Keyboard::new(&vmm)?;
+/// loop {
+///     if keyboard.is_key_down(0x2D)? {
+///         // VK_INSERT toggles the menu...
+///     }
+/// }
+/// ```
+pub struct Keyboard<'a> {
+    process: VmmProcess<'a>,
+    state_addr: u64,
+}
+
+impl<'a> Keyboard<'a> {
+    /// Locates the async key state table of the target.
+    ///
+    /// # Arguments
+    ///
+    /// * `vmm` - Reference to a `Vmm` instance.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `Keyboard`, `DmaError::ProcessNotFound` if `winlogon.exe`
+    /// is not running, or `DmaError::Unsupported` if the table could not be located.
+    pub fn new(vmm: &'a Vmm) -> Result<Self, DmaError> {
+        let process = session_process(vmm)?;
+        let state_addr = session_export(&process, "gafAsyncKeyState")?;
+        Ok(Self { process, state_addr })
+    }
+
+    /// Reads the raw 64-byte async key state table, bypassing the memprocfs cache.
+    pub fn read_state(&self) -> Result<[u8; KEY_STATE_SIZE], DmaError> {
+        let bytes = read_bytes_ex(&self.process, self.state_addr, KEY_STATE_SIZE, ReadFlags::no_cache())?;
+        let mut state = [0u8; KEY_STATE_SIZE];
+        state.copy_from_slice(&bytes);
+        Ok(state)
+    }
+
+    /// Checks whether a key is currently held down on the target.
+    ///
+    /// # Arguments
+    ///
+    /// * `vk_code` - Windows virtual-key code, e.g. `0x01` for the left mouse button.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing whether the key is down, or `DmaError::Read` if the key
+    /// state could not be read. Codes outside `0..=255` are never down.
+    pub fn is_key_down(&self, vk_code: i32) -> Result<bool, DmaError> {
+        if !(0..256).contains(&vk_code) {
+            return Ok(false);
+        }
+        let state = self.read_state()?;
+        Ok(key_down(&state, vk_code as u8))
+    }
+
+    /// Reads the state of the mouse buttons from the key state table.
+    pub fn mouse_buttons(&self) -> Result<MouseButtons, DmaError> {
+        let state = self.read_state()?;
+        Ok(MouseButtons {
+            left: key_down(&state, VK_LBUTTON),
+            right: key_down(&state, VK_RBUTTON),
+            middle: key_down(&state, VK_MBUTTON),
+            x1: key_down(&state, VK_XBUTTON1),
+            x2: key_down(&state, VK_XBUTTON2),
+        })
+    }
+}
+
+/// Checks whether a key is currently held down on the target.
+///
+/// This locates the key state table on every call; create a [`Keyboard`] once to poll
+/// keys in a loop.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `vk_code` - Windows virtual-key code, e.g. `0x2D` for `VK_INSERT`.
+///
+/// # Returns
+///
+/// A `Result` containing whether the key is down, `DmaError::Unsupported` if the key
+/// state is not available on the target, or `DmaError::Read` if it could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// if is_key_down(&vmm, 0x2D)? {
+///     println!("Insert is held");
+/// }
+/// ```
+pub fn is_key_down(vmm: &Vmm, vk_code: i32) -> Result<bool, DmaError> {
+    Keyboard::new(vmm)?.is_key_down(vk_code)
+}
+
+/// Reads the target's mouse cursor position in screen coordinates.
+///
+/// The position is read from `win32kbase.sys!gptCursorAsync` in session space, with
+/// the same prerequisites as [`Keyboard`]: it works on Windows 10, where the symbol is
+/// exported, and returns `DmaError::Unsupported` elsewhere rather than a made-up
+/// position.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+///
+/// # Returns
+///
+/// A `Result` containing the `(x, y)` position, `DmaError::Unsupported` if the cursor
+/// state is not available on the target, or `DmaError::Read` if it could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let (x, y) = get_cursor_position(&vmm)?;
+/// println!("Cursor at {}, {}", x, y);
+/// ```
+pub fn get_cursor_position(vmm: &Vmm) -> Result<(i32, i32), DmaError> {
+    let process = session_process(vmm)?;
+    let cursor = session_export(&process, "gptCursorAsync")?;
+    let position: [i32; 2] = read_process(&process, cursor)?;
+    Ok((position[0], position[1]))
+}
+
+/// Reads which mouse buttons are held down on the target.
+///
+/// This locates the key state table on every call; use [`Keyboard::mouse_buttons`] to
+/// poll in a loop.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+///
+/// # Returns
+///
+/// A `Result` containing the `MouseButtons`, `DmaError::Unsupported` if the key state is
+/// not available on the target, or `DmaError::Read` if it could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// if get_mouse_buttons(&vmm)?.right {
+///     // aim key held...
+/// }
+/// ```
+pub fn get_mouse_buttons(vmm: &Vmm) -> Result<MouseButtons, DmaError> {
+    Keyboard::new(vmm)?.mouse_buttons()
+}
+
+/// Returns whether the down bit of `vk_code` is set in an async key state table.
+fn key_down(state: &[u8; KEY_STATE_SIZE], vk_code: u8) -> bool {
+    let vk_code = vk_code as usize;
+    state[vk_code / 4] & (1 << (vk_code % 4 * 2)) != 0
+}
+
+/// Returns `winlogon.exe` of the interactive session with kernel memory mapped in, which
+/// gives access to the win32k session globals.
+fn session_process<'a>(vmm: &'a Vmm) -> Result<VmmProcess<'a>, DmaError> {
+    let mut process = vmm
+        .process_from_name("winlogon.exe")
+        .map_err(|_| DmaError::ProcessNotFound("winlogon.exe".to_string()))?;
+    process.pid |= PID_WITH_KERNEL_MEMORY;
+    Ok(process)
+}
+
+/// Resolves a session global exported by `win32kbase.sys`.
+fn session_export(process: &VmmProcess, name: &str) -> Result<u64, DmaError> {
+    process
+        .get_proc_address("win32kbase.sys", name)
+        .ok()
+        .filter(|&addr| addr != 0)
+        .ok_or_else(|| DmaError::Unsupported(format!("win32kbase.sys does not export {}", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_down_reads_the_down_bit_of_each_two_bit_entry() {
+        let mut state = [0u8; KEY_STATE_SIZE];
+        // VK 0x01 and 0x03 down, VK 0x02 only has its "pressed since last read" bit set.
+        state[0] = 0b0100_0100 | 0b0010_0000;
+        // VK 0x10 (shift) down.
+        state[4] = 0b0000_0001;
+        // VK 0xFF down, VK 0xFE only has its second bit set.
+        state[63] = 0b0100_0000 | 0b0010_0000;
+
+        let expected = [
+            (0x00, false),
+            (0x01, true),
+            (0x02, false),
+            (0x03, true),
+            (0x10, true),
+            (0x11, false),
+            (0xFE, false),
+            (0xFF, true),
+        ];
+        for (vk_code, down) in expected {
+            assert_eq!(key_down(&state, vk_code), down, "VK 0x{:02X}", vk_code);
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{list_modules, read_bytes, read_process, read_string, resolve_symbol, struct_offset, DmaError, ModuleInfo, Pod, ProcessInfo};
+use memprocfs::{Vmm, VmmProcess};
+use std::collections::HashSet;
+
+/// PID of the System process, whose address space is the kernel's.
+const SYSTEM_PID: u32 = 4;
+
+/// Upper bound on the number of list entries followed, in case the list is corrupt.
+const MAX_LIST_ENTRIES: usize = 0x10000;
+
+/// Lists the modules loaded in the kernel: `ntoskrnl.exe`, the HAL and all drivers.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+///
+/// # Returns
+///
+/// A `Result` containing a `Vec<ModuleInfo>` for every kernel module, or
+/// `DmaError::Map` if the module map of the System process could not be retrieved.
+///
+/// # Examples
+///
+/// ```ignore
+/// for driver in list_kernel_modules(&vmm)? {
+///     println!("{} 0x{:X} 0x{:X}", driver.name, driver.base, driver.size);
+/// }
+/// ```
+pub fn list_kernel_modules(vmm: &Vmm) -> Result<Vec<ModuleInfo>, DmaError> {
+    list_modules(&system_process(vmm))
+}
+
+/// Reads a value of type `T` from kernel memory.
+///
+/// The read goes through the System process (PID 4), whose page tables map the kernel
+/// address space, so any kernel virtual address such as an `EPROCESS` or a global
+/// resolved from symbols can be read.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `addr` - Kernel virtual address to read from.
+///
+/// # Returns
+///
+/// A `Result` containing the value read, or `DmaError::Read` if fewer than
+/// `size_of::<T>()` bytes could be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let flink: u64 = read_kernel(&vmm, ps_active_process_head)?;
+/// ```
+pub fn read_kernel<T: Pod>(vmm: &Vmm, addr: u64) -> Result<T, DmaError> {
+    read_process(&system_process(vmm), addr)
+}
+
+/// Reads exactly `len` bytes from kernel memory.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `addr` - Kernel virtual address to read from.
+/// * `len` - Number of bytes to read.
+///
+/// # Returns
+///
+/// A `Result` containing the bytes read, or `DmaError::Read` if fewer than `len` bytes
+/// could be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let eprocess = read_kernel_bytes(&vmm, info.va_eprocess, 0x800)?;
+/// ```
+pub fn read_kernel_bytes(vmm: &Vmm, addr: u64, len: usize) -> Result<Vec<u8>, DmaError> {
+    read_bytes(&system_process(vmm), addr, len)
+}
+
+/// Enumerates processes by walking the kernel's `ActiveProcessLinks` list directly.
+///
+/// `PsActiveProcessHead` and the `_EPROCESS` field offsets are resolved from the
+/// kernel's debug symbols, so symbol loading must be enabled. Comparing the result with
+/// [`list_processes`](crate::list_processes) shows processes hidden from one of the two
+/// views. Names are the kernel's 15 character `ImageFileName`. The walk stops when it
+/// returns to the list head or to an entry it already followed.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+///
+/// # Returns
+///
+/// A `Result` containing a `ProcessInfo` for every process in the list,
+/// `DmaError::Symbol` if a symbol or offset could not be resolved, or
+/// `DmaError::Read` / `DmaError::NullPointer` if the list could not be followed.
+///
+/// # Examples
+///
+/// ```ignore
+/// for process in walk_eprocess_list(&vmm)? {
+///     println!("{:>6} {} EPROCESS=0x{:X}", process.pid, process.name, process.eprocess);
+/// }
+/// ```
+pub fn walk_eprocess_list(vmm: &Vmm) -> Result<Vec<ProcessInfo>, DmaError> {
+    let head = resolve_symbol(vmm, "nt", "PsActiveProcessHead")?;
+    let links = eprocess_offset(vmm, "ActiveProcessLinks")?;
+    let pid = eprocess_offset(vmm, "UniqueProcessId")?;
+    let ppid = eprocess_offset(vmm, "InheritedFromUniqueProcessId")?;
+    let image_file_name = eprocess_offset(vmm, "ImageFileName")?;
+    let wow64 = eprocess_offset(vmm, "WoW64Process")?;
+
+    let system = system_process(vmm);
+    let mut processes = Vec::new();
+    let mut visited = HashSet::new();
+    let mut entry = read_link(&system, head)?;
+    // A corrupt `Flink` may point back into the list, so every entry is followed once.
+    while entry != head && processes.len() < MAX_LIST_ENTRIES && visited.insert(entry) {
+        let eprocess = entry.wrapping_sub(links);
+        processes.push(ProcessInfo {
+            pid: read_process::<u64>(&system, eprocess.wrapping_add(pid))? as u32,
+            name: read_string(&system, eprocess.wrapping_add(image_file_name), 15)?,
+            ppid: read_process::<u64>(&system, eprocess.wrapping_add(ppid))? as u32,
+            is_wow64: read_process::<u64>(&system, eprocess.wrapping_add(wow64))? != 0,
+            eprocess,
+        });
+        entry = read_link(&system, entry)?;
+    }
+
+    Ok(processes)
+}
+
+/// Reads the `Flink` of the `LIST_ENTRY` at `entry`, which must not be null.
+fn read_link(system: &VmmProcess, entry: u64) -> Result<u64, DmaError> {
+    match read_process::<u64>(system, entry)? {
+        0 => Err(DmaError::NullPointer { addr: entry }),
+        flink => Ok(flink),
+    }
+}
+
+/// Resolves the offset of a field of `_EPROCESS` from the kernel's debug symbols.
+pub(crate) fn eprocess_offset(vmm: &Vmm, field: &str) -> Result<u64, DmaError> {
+    struct_offset(vmm, "nt", "_EPROCESS", field).map(u64::from)
+}
+
+/// Returns the System process, through which kernel memory is accessed.
+pub(crate) fn system_process<'a>(vmm: &'a Vmm<'a>) -> VmmProcess<'a> {
+    VmmProcess { vmm, pid: SYSTEM_PID }
+}
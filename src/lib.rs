@@ -1,6 +1,68 @@
-use memprocfs::{Vmm, VmmProcess, CONFIG_OPT_PROCESS_DTB};
+use memprocfs::{Vmm, VmmProcess};
 use std::{thread, time};
-use std::error::Error;
+
+mod backend;
+mod builder;
+mod cache;
+mod device;
+mod dump;
+mod error;
+mod hooks;
+mod inject;
+mod input;
+mod kernel;
+mod list;
+mod memory;
+mod module;
+mod offsets;
+mod pe;
+mod physical;
+mod pod;
+mod pointer;
+mod process;
+mod refresh;
+mod region;
+mod resilient;
+mod scan;
+mod scatter;
+mod session;
+mod shared;
+mod strings;
+mod symbols;
+mod vector;
+mod vfs;
+mod view;
+
+pub use backend::{MemoryReader, MockMemory};
+pub use builder::VmmBuilder;
+pub use cache::{DtbCache, ModuleCache};
+pub use device::*;
+pub use dump::*;
+pub use error::DmaError;
+pub use hooks::*;
+pub use inject::*;
+pub use input::*;
+pub use kernel::*;
+pub use list::*;
+pub use memory::*;
+pub use module::*;
+pub use offsets::OffsetTable;
+pub use physical::*;
+pub use pod::Pod;
+pub use pointer::Pointer;
+pub use process::*;
+pub use refresh::*;
+pub use region::*;
+pub use resilient::{with_retry, ResilientVmm};
+pub use scan::*;
+pub use scatter::{ScatterReader, ScatterWriter, StructReader, StructValues};
+pub use session::{attach, Session};
+pub use shared::SharedVmm;
+pub use strings::*;
+pub use symbols::*;
+pub use vector::*;
+pub use vfs::*;
+pub use view::{snapshot, MemView};
 
 /// Initializes a `Vmm` instance with the provided path and arguments.
 ///
@@ -11,18 +73,17 @@ use std::error::Error;
 ///
 /// # Returns
 ///
-/// A `Result` containing the `Vmm` instance on success, or a boxed error on failure.
+/// A `Result` containing the `Vmm` instance on success, or `DmaError::VmmInit` on failure.
 ///
 /// # Examples
 ///
-/// ```
+/// ```ignore
 /// let vmm_path = "path/to/vmm.dll";
 /// let args = vec!["", "-device", "fpga"];
 /// let vmm = init(vmm_path, &args).expect("Failed to initialize Vmm");
 /// ```
-pub fn init<'a>(vmm_path: &'a str, args: &'a Vec<&'a str>) -> Result<Vmm<'a>, Box<dyn std::error::Error + 'a>> {
-    let vmm = Vmm::new(vmm_path, args)?;
-    Ok(vmm)
+pub fn init<'a>(vmm_path: &'a str, args: &'a Vec<&'a str>) -> Result<Vmm<'a>, DmaError> {
+    Vmm::new(vmm_path, args).map_err(|e| DmaError::VmmInit(e.to_string()))
 }
 
 /// Retrieves the Windows version from the VMM instance.
@@ -37,11 +98,11 @@ pub fn init<'a>(vmm_path: &'a str, args: &'a Vec<&'a str>) -> Result<Vmm<'a>, Bo
 ///
 /// # Examples
 ///
-/// ```
+/// ```ignore
 /// let winver = get_winver(&vmm).expect("Failed to get Windows version");
 /// println!("Windows version: {}", winver);
 /// ```
-pub fn get_winver(vmm: &Vmm) -> Result<String, Box<dyn Error>> {
+pub fn get_winver(vmm: &Vmm) -> Result<String, DmaError> {
     Ok(vmm.kernel().build().to_string())
 }
 
@@ -58,25 +119,83 @@ pub fn get_winver(vmm: &Vmm) -> Result<String, Box<dyn Error>> {
 ///
 /// # Examples
 ///
-/// ```
+/// ```ignore
 /// let pid = find_process(&vmm, "smss.exe").expect("Process not found");
 /// println!("PID: {}", pid);
 /// ```
 pub fn find_process(vmm: &Vmm, process_name: &str) -> Option<u32> {
+    find_process_handle(vmm, process_name).map(|process| process.pid)
+}
+
+/// Finds a process by its name and returns its `VmmProcess` handle.
+///
+/// Unlike [`find_process`], the handle is returned directly so no second
+/// `process_from_pid` lookup is needed.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `process_name` - Name of the process to find.
+///
+/// # Returns
+///
+/// An `Option<VmmProcess>` containing the process handle if found, or `None` if not found.
+///
+/// # Examples
+///
+/// ```ignore
+/// let process = find_process_handle(&vmm, "smss.exe").expect("Process not found");
+/// println!("PID: {}", process.pid);
+/// ```
+pub fn find_process_handle<'a>(vmm: &'a Vmm, process_name: &str) -> Option<VmmProcess<'a>> {
     match vmm.process_from_name(process_name) {
-        Ok(process) => Some(process.pid),
+        Ok(process) => Some(process),
         Err(e) => {
-            println!("Failed to find {}: {}", process_name, e);
+            log::warn!("Failed to find {}: {}", process_name, e);
             None
         }
     }
 }
 
-/// Finds the base address of a module within a process.
+/// Waits for a process to appear, polling by name until it is found or a timeout elapses.
 ///
 /// # Arguments
 ///
 /// * `vmm` - Reference to a `Vmm` instance.
+/// * `process_name` - Name of the process to wait for.
+/// * `timeout` - Maximum time to wait.
+/// * `poll_interval` - Time to sleep between lookups.
+///
+/// # Returns
+///
+/// A `Result` containing the PID once the process is found, or `DmaError::Timeout` if
+/// it did not appear within `timeout`.
+///
+/// # Examples
+///
+/// ```ignore
+/// let pid = wait_for_process(&vmm, "game.exe", Duration::from_secs(60), Duration::from_millis(500))?;
+/// println!("PID: {}", pid);
+/// ```
+pub fn wait_for_process(vmm: &Vmm, process_name: &str, timeout: time::Duration, poll_interval: time::Duration) -> Result<u32, DmaError> {
+    let start = time::Instant::now();
+
+    loop {
+        if let Ok(process) = vmm.process_from_name(process_name) {
+            return Ok(process.pid);
+        }
+        if start.elapsed() >= timeout {
+            return Err(DmaError::Timeout);
+        }
+        thread::sleep(poll_interval);
+    }
+}
+
+/// Finds the base address of a module within a process.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance, or any other [`MemoryReader`].
 /// * `process_pid` - PID of the process.
 /// * `module_name` - Name of the module to find.
 ///
@@ -86,26 +205,48 @@ pub fn find_process(vmm: &Vmm, process_name: &str) -> Option<u32> {
 ///
 /// # Examples
 ///
-/// ```
+/// ```ignore
 /// let base_address = find_base_address(&vmm, pid, "smss.exe").expect("Module not found");
 /// println!("Base address: 0x{:X}", base_address);
 /// ```
-pub fn find_base_address(vmm: &Vmm, process_pid: u32, module_name: &str) -> Option<u64> {
-    if let Ok(process) = vmm.process_from_pid(process_pid) {
-        match process.get_module_base(module_name) {
-            Ok(base) => Some(base),
-            Err(e) => {
-                println!("Failed to find {} base: {}", module_name, e);
-                None
-            }
+pub fn find_base_address<M: MemoryReader + ?Sized>(vmm: &M, process_pid: u32, module_name: &str) -> Option<u64> {
+    match vmm.module_base(process_pid, module_name) {
+        Ok(base) => Some(base),
+        Err(e) => {
+            log::warn!("Failed to find {} base: {}", module_name, e);
+            None
+        }
+    }
+}
+
+/// Options controlling how [`fix_cr3_with_options`] waits for procinfo to complete.
+#[derive(Debug, Clone, Copy)]
+pub struct Cr3Options {
+    /// Time to sleep between progress checks.
+    pub poll_interval: time::Duration,
+    /// Maximum time to wait for procinfo, or `None` to wait indefinitely.
+    pub timeout: Option<time::Duration>,
+    /// Progress percentage at which procinfo is considered complete.
+    pub ready_percent: u32,
+}
+
+impl Default for Cr3Options {
+    fn default() -> Self {
+        Self {
+            poll_interval: time::Duration::from_millis(500),
+            timeout: None,
+            ready_percent: 100,
         }
-    } else {
-        None
     }
 }
 
 /// Attempts to fix the CR3 register for a given process and module.
 ///
+/// Waits indefinitely for procinfo to complete; use [`fix_cr3_with_options`] to bound
+/// the wait. When the `Vmm` was built with a memory map (see [`VmmBuilder::memmap`]),
+/// memprocfs usually resolves the DTB on its own and this is only needed for processes
+/// that hide their CR3.
+///
 /// # Arguments
 ///
 /// * `vmm` - Reference to a `Vmm` instance.
@@ -115,11 +256,12 @@ pub fn find_base_address(vmm: &Vmm, process_pid: u32, module_name: &str) -> Opti
 ///
 /// # Returns
 ///
-/// A `Result<bool, Box<dyn Error>>` indicating success (`true`) or failure (`false`).
+/// A `Result<bool, DmaError>` indicating success (`true`) or failure (`false`), or
+/// `DmaError::Vfs` if the DTB list could not be read.
 ///
 /// # Examples
 ///
-/// ```
+/// ```ignore
 /// let success = fix_cr3(&vmm, &process, "smss.exe", pid).expect("Failed to fix CR3");
 /// if success {
 ///     println!("Successfully fixed CR3 register.");
@@ -127,37 +269,296 @@ pub fn find_base_address(vmm: &Vmm, process_pid: u32, module_name: &str) -> Opti
 ///     println!("Failed to fix CR3 register.");
 /// }
 /// ```
-pub fn fix_cr3(vmm: &Vmm, process: &VmmProcess, target_module: &str, pid: u32) -> Result<bool, Box<dyn Error>> {
-    let mut possible_dtbs = Vec::new();
+pub fn fix_cr3(vmm: &Vmm, process: &VmmProcess, target_module: &str, pid: u32) -> Result<bool, DmaError> {
+    fix_cr3_with_options(vmm, process, target_module, pid, &Cr3Options::default())
+}
+
+/// Attempts to fix the CR3 register for a given process and module, with a configurable
+/// poll interval and timeout for the procinfo wait.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `target_module` - Name of the target module.
+/// * `pid` - PID of the process.
+/// * `options` - Poll interval, timeout and ready percentage to use.
+///
+/// # Returns
+///
+/// A `Result<bool, DmaError>` indicating success (`true`) or failure (`false`),
+/// `DmaError::Timeout` if procinfo did not complete in time, or `DmaError::Vfs` if the
+/// DTB list could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let options = Cr3Options { timeout: Some(Duration::from_secs(30)), ..Default::default() };
+/// let success = fix_cr3_with_options(&vmm, &process, "smss.exe", pid, &options)?;
+/// ```
+pub fn fix_cr3_with_options(vmm: &Vmm, process: &VmmProcess, target_module: &str, pid: u32, options: &Cr3Options) -> Result<bool, DmaError> {
+    Ok(fix_cr3_value_with_options(vmm, process, target_module, pid, options)?.is_some())
+}
+
+/// Attempts to fix the CR3 register for a given process and module, returning the DTB
+/// that was applied.
+///
+/// Waits indefinitely for procinfo to complete; use [`fix_cr3_value_with_options`] to
+/// bound the wait.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `target_module` - Name of the target module.
+/// * `pid` - PID of the process.
+///
+/// # Returns
+///
+/// A `Result<Option<u64>, DmaError>` containing the working DTB, `None` if no candidate
+/// worked, or `DmaError::Vfs` if the DTB list could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// if let Some(dtb) = fix_cr3_value(&vmm, &process, "smss.exe", pid)? {
+///     println!("Fixed CR3 with DTB 0x{:X}", dtb);
+/// }
+/// ```
+pub fn fix_cr3_value(vmm: &Vmm, process: &VmmProcess, target_module: &str, pid: u32) -> Result<Option<u64>, DmaError> {
+    fix_cr3_value_with_options(vmm, process, target_module, pid, &Cr3Options::default())
+}
+
+/// Attempts to fix the CR3 register for a given process and module, returning the DTB
+/// that was applied, with a configurable poll interval and timeout for the procinfo wait.
+///
+/// The first candidate DTB with which `target_module` resolves is left applied to the
+/// process.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `target_module` - Name of the target module.
+/// * `pid` - PID of the process.
+/// * `options` - Poll interval, timeout and ready percentage to use.
+///
+/// # Returns
+///
+/// A `Result<Option<u64>, DmaError>` containing the working DTB, `None` if no candidate
+/// worked, `DmaError::Timeout` if procinfo did not complete in time, or `DmaError::Vfs`
+/// if the DTB list could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let options = Cr3Options { timeout: Some(Duration::from_secs(30)), ..Default::default() };
+/// let dtb = fix_cr3_value_with_options(&vmm, &process, "smss.exe", pid, &options)?;
+/// ```
+pub fn fix_cr3_value_with_options(vmm: &Vmm, process: &VmmProcess, target_module: &str, pid: u32, options: &Cr3Options) -> Result<Option<u64>, DmaError> {
+    debug_assert_eq!(process.pid, pid, "process and pid refer to different processes");
+    resolve_dtb(vmm, target_module, pid, options)
+}
+
+/// Waits for procinfo, then applies the first candidate DTB with which `target_module`
+/// resolves.
+pub(crate) fn resolve_dtb<M: MemoryReader + ?Sized>(reader: &M, target_module: &str, pid: u32, options: &Cr3Options) -> Result<Option<u64>, DmaError> {
+    let start = time::Instant::now();
 
     loop {
-        if let Ok(progress_percent) = vmm.vfs_read("\\misc\\procinfo\\progress_percent.txt", 3, 0) {
-            if progress_percent.len() == 3 {
-                break;
-            }
+        let progress = reader
+            .vfs_read(VFS_PROCINFO_PROGRESS, 0x10)
+            .ok()
+            .and_then(|progress| parse_progress_percent(&progress));
+        if progress.is_some_and(|percent| percent >= options.ready_percent) {
+            break;
+        }
+        if options.timeout.is_some_and(|timeout| start.elapsed() >= timeout) {
+            return Err(DmaError::Timeout);
         }
-        thread::sleep(time::Duration::from_millis(500));
+        thread::sleep(options.poll_interval);
     }
 
-    let dtbs = vmm.vfs_read("\\misc\\procinfo\\dtb.txt", 0x80000, 0)?;
-    let result = String::from_utf8_lossy(&dtbs);
+    let dtbs = reader.vfs_read(VFS_PROCINFO_DTB, 0x80000)?;
+    let possible_dtbs = parse_dtb_lines(&String::from_utf8_lossy(&dtbs));
 
-    for line in result.lines() {
-        let mut split = line.split_whitespace().filter(|s| !s.is_empty());
-        if let (Some(_), Some("0"), Some(dtb)) = (split.next(), split.next(), split.next()) {
-            if let Ok(dtb_value) = u64::from_str_radix(dtb, 16) {
-                possible_dtbs.push(dtb_value);
-            }
+    log::debug!("Trying {} candidate DTBs for PID {}", possible_dtbs.len(), pid);
+    for dtb in &possible_dtbs {
+        if try_dtb(reader, target_module, pid, *dtb) {
+            log::debug!("Fixed CR3 for PID {} with DTB 0x{:X}", pid, dtb);
+            return Ok(Some(*dtb));
         }
     }
 
-    for dtb in &possible_dtbs {
-        if vmm.set_config(CONFIG_OPT_PROCESS_DTB | pid as u64, *dtb).is_ok() {
-            if process.get_module_base(target_module).is_ok() {
-                return Ok(true);
-            }
+    log::warn!("No working DTB found for PID {} among {} candidates", pid, possible_dtbs.len());
+    Ok(None)
+}
+
+/// Parses the contents of `progress_percent.txt`, ignoring surrounding whitespace.
+fn parse_progress_percent(bytes: &[u8]) -> Option<u32> {
+    String::from_utf8_lossy(bytes).trim().parse().ok()
+}
+
+/// Parses the candidate DTBs out of the procinfo `dtb.txt` listing.
+///
+/// Each line holds an index, the PID a DTB was attributed to and the DTB in hex,
+/// optionally followed by more columns such as the kernel address and process name.
+/// Only DTBs attributed to PID `0`, i.e. not matched to any process, are candidates.
+/// The PID may be zero-padded, the DTB may use either hex case and a `0x` prefix, and
+/// header, blank and malformed lines are skipped. Duplicates are removed, keeping the
+/// order of first appearance.
+///
+/// # Arguments
+///
+/// * `text` - Contents of `\misc\procinfo\dtb.txt`.
+///
+/// # Returns
+///
+/// A `Vec<u64>` of candidate DTBs, empty if none were found.
+///
+/// # Examples
+///
+/// ```ignore
+/// let dtbs = parse_dtb_lines("0000 0 1ad000 ffffa00000000000 ---\n");
+/// assert_eq!(dtbs, vec![0x1AD000]);
+/// ```
+pub fn parse_dtb_lines(text: &str) -> Vec<u64> {
+    let mut dtbs = Vec::new();
+    for line in text.lines() {
+        let mut columns = line.split_whitespace();
+        let (Some(_), Some(pid), Some(dtb)) = (columns.next(), columns.next(), columns.next()) else {
+            continue;
+        };
+        if pid.parse::<u32>() != Ok(0) {
+            continue;
+        }
+        let dtb = dtb.strip_prefix("0x").or_else(|| dtb.strip_prefix("0X")).unwrap_or(dtb);
+        match u64::from_str_radix(dtb, 16) {
+            Ok(dtb) if dtb != 0 && !dtbs.contains(&dtb) => dtbs.push(dtb),
+            Ok(_) => {}
+            Err(_) => log::debug!("Skipping malformed dtb.txt line: {:?}", line),
+        }
+    }
+    dtbs
+}
+
+/// Applies `dtb` to the process and checks that `target_module` resolves with it.
+pub(crate) fn try_dtb<M: MemoryReader + ?Sized>(reader: &M, target_module: &str, pid: u32, dtb: u64) -> bool {
+    reader.set_process_dtb(pid, dtb).is_ok() && reader.module_base(pid, target_module).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAME_PID: u32 = 1234;
+
+    fn procinfo(dtb_txt: &str) -> MockMemory {
+        MockMemory::new()
+            .with_file(VFS_PROCINFO_PROGRESS, b"100")
+            .with_file(VFS_PROCINFO_DTB, dtb_txt.as_bytes())
+            .with_module(GAME_PID, "game.exe", 0x7FF6_0000_0000)
+    }
+
+    fn options() -> Cr3Options {
+        Cr3Options {
+            poll_interval: time::Duration::from_millis(1),
+            timeout: Some(time::Duration::from_millis(20)),
+            ..Default::default()
         }
     }
 
-    Ok(false)
+    #[test]
+    fn parse_progress_percent_ignores_whitespace() {
+        assert_eq!(parse_progress_percent(b"100"), Some(100));
+        assert_eq!(parse_progress_percent(b"  9\n"), Some(9));
+        assert_eq!(parse_progress_percent(b"42\r\n"), Some(42));
+        assert_eq!(parse_progress_percent(b""), None);
+        assert_eq!(parse_progress_percent(b"n/a"), None);
+    }
+
+    #[test]
+    fn resolve_dtb_waits_for_the_ready_percentage() {
+        let mock = procinfo("0000 0 1ad000 ---\n")
+            .with_file(VFS_PROCINFO_PROGRESS, b" 95\n")
+            .with_dtb(GAME_PID, 0x1AD000);
+        assert!(matches!(resolve_dtb(&mock, "game.exe", GAME_PID, &options()), Err(DmaError::Timeout)));
+        let options = Cr3Options { ready_percent: 90, ..options() };
+        assert_eq!(resolve_dtb(&mock, "game.exe", GAME_PID, &options).unwrap(), Some(0x1AD000));
+    }
+
+    #[test]
+    fn parse_dtb_lines_reads_unassigned_dtbs() {
+        let text = "0000 0 1ad000 ffffa00000000000 ---\n0001 4 1be000 ffffa00000001000 System\n0002 0 2cf000 ffffa00000002000 ---\n";
+        assert_eq!(parse_dtb_lines(text), vec![0x1AD000, 0x2CF000]);
+    }
+
+    #[test]
+    fn parse_dtb_lines_handles_windows_line_endings() {
+        assert_eq!(parse_dtb_lines("0000 0 1ad000 ---\r\n0001 0 2cf000 ---\r\n"), vec![0x1AD000, 0x2CF000]);
+    }
+
+    #[test]
+    fn parse_dtb_lines_accepts_hex_casing_and_prefixes() {
+        let text = "0000 0 1AD000\n0001 0 0x2bE000\n0002 0 0X3CF000\n";
+        assert_eq!(parse_dtb_lines(text), vec![0x1AD000, 0x2BE000, 0x3CF000]);
+    }
+
+    #[test]
+    fn parse_dtb_lines_accepts_varying_index_and_pid_fields() {
+        let text = "   0x0a   00000000  1ad000\n\t7\t0\t2be000\textra\tcolumns here\n";
+        assert_eq!(parse_dtb_lines(text), vec![0x1AD000, 0x2BE000]);
+    }
+
+    #[test]
+    fn parse_dtb_lines_skips_headers_and_malformed_lines() {
+        let text = "Index PID DTB KernelAddr Name\n\n0000 0\n0001 x 1ad000\n0002 0 zzz\n0003 0 0\n0004 0 2be000\n";
+        assert_eq!(parse_dtb_lines(text), vec![0x2BE000]);
+    }
+
+    #[test]
+    fn parse_dtb_lines_removes_duplicates() {
+        assert_eq!(parse_dtb_lines("0000 0 1ad000\n0001 0 2be000\n0002 0 1AD000\n"), vec![0x1AD000, 0x2BE000]);
+    }
+
+    #[test]
+    fn find_base_address_resolves_loaded_modules() {
+        let mock = MockMemory::new().with_module(GAME_PID, "game.exe", 0x7FF6_0000_0000);
+        assert_eq!(find_base_address(&mock, GAME_PID, "GAME.EXE"), Some(0x7FF6_0000_0000));
+        assert_eq!(find_base_address(&mock, GAME_PID, "client.dll"), None);
+        assert_eq!(find_base_address(&mock, 1, "game.exe"), None);
+    }
+
+    #[test]
+    fn resolve_dtb_applies_the_first_working_candidate() {
+        let dtb_txt = "0000 0 1ad000 ffffa00000000000 ---\n0001 0 2be000 ffffa00000001000 ---\n0002 0 3cf000 ffffa00000002000 ---\n";
+        let mock = procinfo(dtb_txt).with_dtb(GAME_PID, 0x2BE000);
+        assert_eq!(resolve_dtb(&mock, "game.exe", GAME_PID, &options()).unwrap(), Some(0x2BE000));
+        assert_eq!(mock.dtb(GAME_PID), Some(0x2BE000));
+    }
+
+    #[test]
+    fn resolve_dtb_skips_dtbs_assigned_to_processes() {
+        let mock = procinfo("0000 4 1ad000 ffffa00000000000 System\n").with_dtb(GAME_PID, 0x1AD000);
+        assert_eq!(resolve_dtb(&mock, "game.exe", GAME_PID, &options()).unwrap(), None);
+        assert_eq!(mock.dtb(GAME_PID), None);
+    }
+
+    #[test]
+    fn resolve_dtb_returns_none_when_no_candidate_works() {
+        let mock = procinfo("0000 0 1ad000 ffffa00000000000 ---\n").with_dtb(GAME_PID, 0x2BE000);
+        assert_eq!(resolve_dtb(&mock, "game.exe", GAME_PID, &options()).unwrap(), None);
+    }
+
+    #[test]
+    fn resolve_dtb_times_out_while_procinfo_is_running() {
+        let mock = MockMemory::new().with_file(VFS_PROCINFO_PROGRESS, b"42");
+        assert!(matches!(resolve_dtb(&mock, "game.exe", GAME_PID, &options()), Err(DmaError::Timeout)));
+    }
+
+    #[test]
+    fn resolve_dtb_reports_a_missing_dtb_list() {
+        let mock = MockMemory::new().with_file(VFS_PROCINFO_PROGRESS, b"100");
+        assert!(matches!(resolve_dtb(&mock, "game.exe", GAME_PID, &options()), Err(DmaError::Vfs(_))));
+    }
 }
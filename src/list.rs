@@ -0,0 +1,179 @@
+use crate::memory::from_bytes;
+use crate::{read_bytes, DmaError, Pod};
+use memprocfs::VmmProcess;
+use std::collections::HashSet;
+use std::mem;
+
+/// Reads the nodes of a singly or doubly linked list whose `next` pointers point to the
+/// start of the next node, as in most game entity lists.
+///
+/// Starting at `head`, each node is read as a `T` and the pointer at
+/// `node + next_offset` is followed. The walk stops at a null pointer, when it returns
+/// to `head` or any other node already read (circular lists), or after `max_nodes`
+/// nodes, in which case the last node's `next` pointer is not read. For kernel-style
+/// lists whose links point to a `LIST_ENTRY` embedded in the next node, use
+/// [`read_list_entries`].
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `head` - Address of the first node.
+/// * `next_offset` - Offset of the `next` pointer within a node.
+/// * `max_nodes` - Maximum number of nodes to read.
+///
+/// # Returns
+///
+/// A `Result` containing the nodes in list order, empty if `head` is null, or
+/// `DmaError::Read` if a node or pointer could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let entities: Vec<Entity> = read_linked_list(&process, first_entity, 0x8, 1024)?;
+/// ```
+pub fn read_linked_list<T: Pod>(process: &VmmProcess, head: u64, next_offset: u64, max_nodes: usize) -> Result<Vec<T>, DmaError> {
+    read_linked_list_with(|addr, len| read_bytes(process, addr, len), head, next_offset, max_nodes)
+}
+
+/// Implements [`read_linked_list`] on top of `read`, which must return exactly `len` bytes.
+fn read_linked_list_with<T: Pod>(read: impl Fn(u64, usize) -> Result<Vec<u8>, DmaError>, head: u64, next_offset: u64, max_nodes: usize) -> Result<Vec<T>, DmaError> {
+    let mut nodes = Vec::new();
+    let mut visited = HashSet::new();
+    let mut node = head;
+    while node != 0 && nodes.len() < max_nodes && visited.insert(node) {
+        nodes.push(read_value(&read, node)?);
+        if nodes.len() == max_nodes {
+            break;
+        }
+        node = read_value(&read, node.wrapping_add(next_offset))?;
+    }
+    Ok(nodes)
+}
+
+/// Reads the structures linked by a circular `LIST_ENTRY` list with a separate head.
+///
+/// `head` is the list head, e.g. `PsActiveProcessHead`, and is not part of any node.
+/// Its `Flink` and those of every node point to the `LIST_ENTRY` at `link_offset`
+/// inside the next node, so each node is read from `link - link_offset`. The walk stops
+/// when it returns to `head`, at a null link, at a link already followed, or after
+/// `max_nodes` nodes, in which case the last node's `Flink` is not read.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process,
+///   e.g. the System process for kernel lists.
+/// * `head` - Address of the list head.
+/// * `link_offset` - Offset of the `LIST_ENTRY` within a node.
+/// * `max_nodes` - Maximum number of nodes to read.
+///
+/// # Returns
+///
+/// A `Result` containing the nodes in list order, or `DmaError::Read` if a node or
+/// link could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let system = vmm.process_from_pid(4)?;
+/// let modules: Vec<LdrDataTableEntry> = read_list_entries(&system, ps_loaded_module_list, 0, 512)?;
+/// ```
+pub fn read_list_entries<T: Pod>(process: &VmmProcess, head: u64, link_offset: u64, max_nodes: usize) -> Result<Vec<T>, DmaError> {
+    read_list_entries_with(|addr, len| read_bytes(process, addr, len), head, link_offset, max_nodes)
+}
+
+/// Implements [`read_list_entries`] on top of `read`, which must return exactly `len` bytes.
+fn read_list_entries_with<T: Pod>(read: impl Fn(u64, usize) -> Result<Vec<u8>, DmaError>, head: u64, link_offset: u64, max_nodes: usize) -> Result<Vec<T>, DmaError> {
+    let mut nodes = Vec::new();
+    if max_nodes == 0 {
+        return Ok(nodes);
+    }
+    let mut visited = HashSet::new();
+    let mut link: u64 = read_value(&read, head)?;
+    while link != 0 && link != head && visited.insert(link) {
+        nodes.push(read_value(&read, link.wrapping_sub(link_offset))?);
+        if nodes.len() == max_nodes {
+            break;
+        }
+        link = read_value(&read, link)?;
+    }
+    Ok(nodes)
+}
+
+/// Reads a value of type `T` through `read`.
+fn read_value<T: Pod>(read: &impl Fn(u64, usize) -> Result<Vec<u8>, DmaError>, addr: u64) -> Result<T, DmaError> {
+    Ok(from_bytes(&read(addr, mem::size_of::<T>())?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MemoryReader, MockMemory};
+
+    const PID: u32 = 1;
+    const BASE: u64 = 0x1000;
+
+    /// Builds memory with 16-byte nodes at `BASE + 0x10 * index`, each holding its index
+    /// as a `u64` and then the given `next` pointer.
+    fn nodes(next: &[u64]) -> MockMemory {
+        let bytes = next
+            .iter()
+            .enumerate()
+            .flat_map(|(index, next)| [(index as u64).to_le_bytes(), next.to_le_bytes()])
+            .flatten()
+            .collect();
+        MockMemory::new().with_memory(PID, BASE, bytes)
+    }
+
+    fn reader(mock: &MockMemory) -> impl Fn(u64, usize) -> Result<Vec<u8>, DmaError> + '_ {
+        |addr, len| mock.read_memory(PID, addr, len)
+    }
+
+    fn node(index: u64) -> u64 {
+        BASE + 0x10 * index
+    }
+
+    #[test]
+    fn linked_list_stops_at_null() {
+        let mock = nodes(&[node(1), node(2), 0]);
+        assert_eq!(read_linked_list_with::<u64>(reader(&mock), node(0), 8, 16).unwrap(), vec![0, 1, 2]);
+        assert!(read_linked_list_with::<u64>(reader(&mock), 0, 8, 16).unwrap().is_empty());
+    }
+
+    #[test]
+    fn linked_list_stops_when_returning_to_head_or_a_visited_node() {
+        let circular = nodes(&[node(1), node(2), node(0)]);
+        assert_eq!(read_linked_list_with::<u64>(reader(&circular), node(0), 8, 16).unwrap(), vec![0, 1, 2]);
+        let cycle = nodes(&[node(1), node(2), node(1)]);
+        assert_eq!(read_linked_list_with::<u64>(reader(&cycle), node(0), 8, 16).unwrap(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn linked_list_stops_at_max_nodes_without_following_the_last_link() {
+        // The last node's `next` points at unmapped memory.
+        let mock = nodes(&[node(1), 0xDEAD_0000]);
+        assert_eq!(read_linked_list_with::<u64>(reader(&mock), node(0), 8, 2).unwrap(), vec![0, 1]);
+        assert!(read_linked_list_with::<u64>(reader(&mock), node(0), 8, 3).is_err());
+        assert!(read_linked_list_with::<u64>(reader(&mock), node(0), 8, 0).unwrap().is_empty());
+    }
+
+    /// `LIST_ENTRY` lists with the head at node 0 and the link at offset 8 of each node.
+    #[test]
+    fn list_entries_stop_at_head_null_and_cycles() {
+        let link = |index| node(index) + 8;
+        let circular = nodes(&[link(1), link(2), link(0)]);
+        assert_eq!(read_list_entries_with::<u64>(reader(&circular), link(0), 8, 16).unwrap(), vec![1, 2]);
+        let null = nodes(&[link(1), link(2), 0]);
+        assert_eq!(read_list_entries_with::<u64>(reader(&null), link(0), 8, 16).unwrap(), vec![1, 2]);
+        let cycle = nodes(&[link(1), link(2), link(1)]);
+        assert_eq!(read_list_entries_with::<u64>(reader(&cycle), link(0), 8, 16).unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn list_entries_stop_at_max_nodes_without_following_the_last_link() {
+        let link = |index| node(index) + 8;
+        let mock = nodes(&[link(1), link(2), 0xDEAD_0000]);
+        assert_eq!(read_list_entries_with::<u64>(reader(&mock), link(0), 8, 2).unwrap(), vec![1, 2]);
+        assert!(read_list_entries_with::<u64>(reader(&mock), link(0), 8, 3).is_err());
+        assert!(read_list_entries_with::<u64>(reader(&mock), link(0), 8, 0).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,736 @@
+use crate::{is_wow64, DmaError, Pod, ScatterReader};
+use memprocfs::{
+    Vmm, VmmProcess, FLAG_FORCECACHE_READ, FLAG_NOCACHE, FLAG_NOPAGING, FLAG_NO_PREDICTIVE_READ, FLAG_ZEROPAD_ON_FAIL,
+};
+use std::ops::{BitOr, BitOrAssign};
+use std::{mem, ptr, slice};
+
+/// Options controlling how memprocfs performs a read.
+///
+/// Flags are combined with `|`, e.g. `ReadFlags::no_cache() | ReadFlags::zeropad()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReadFlags(u64);
+
+impl ReadFlags {
+    /// No flags: reads may be served from the cache and fail on unreadable pages.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Bypasses the memprocfs cache and always reads from the device, for values that
+    /// change every frame.
+    pub const fn no_cache() -> Self {
+        Self(FLAG_NOCACHE)
+    }
+
+    /// Fills unreadable bytes with zeros instead of failing the read.
+    pub const fn zeropad() -> Self {
+        Self(FLAG_ZEROPAD_ON_FAIL)
+    }
+
+    /// Serves the read from the cache only, without touching the device.
+    pub const fn force_cache() -> Self {
+        Self(FLAG_FORCECACHE_READ)
+    }
+
+    /// Does not try to retrieve paged-out memory from the page file.
+    pub const fn no_paging() -> Self {
+        Self(FLAG_NOPAGING)
+    }
+
+    /// Does not read ahead beyond the requested range.
+    pub const fn no_predictive_read() -> Self {
+        Self(FLAG_NO_PREDICTIVE_READ)
+    }
+
+    /// Returns the raw memprocfs `FLAG_*` value.
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Returns whether all flags in `other` are set.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for ReadFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for ReadFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Reads a value of type `T` from the memory of a process identified by its PID.
+///
+/// `T` must implement [`Pod`], since the bytes read can hold any bit pattern.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `pid` - PID of the process to read from.
+/// * `addr` - Virtual address to read from.
+///
+/// # Returns
+///
+/// A `Result` containing the value read on success, `DmaError::ProcessNotFound` if the
+/// process could not be found, or `DmaError::Read` if fewer than `size_of::<T>()` bytes
+/// were read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let health: i32 = read(&vmm, pid, base_address + 0x100)?;
+/// println!("Health: {}", health);
+/// ```
+pub fn read<T: Pod>(vmm: &Vmm, pid: u32, addr: u64) -> Result<T, DmaError> {
+    let process = vmm
+        .process_from_pid(pid)
+        .map_err(|_| DmaError::ProcessNotFound(format!("with PID {}", pid)))?;
+    read_process(&process, addr)
+}
+
+/// Reads a value of type `T` from the memory of an already resolved process.
+///
+/// Prefer this over [`read`] when reading in a loop, as it skips the PID lookup.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address to read from.
+///
+/// # Returns
+///
+/// A `Result` containing the value read on success, or `DmaError::Read` if fewer than
+/// `size_of::<T>()` bytes were read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let process = vmm.process_from_pid(pid)?;
+/// let health: i32 = read_process(&process, base_address + 0x100)?;
+/// ```
+pub fn read_process<T: Pod>(process: &VmmProcess, addr: u64) -> Result<T, DmaError> {
+    let buffer = read_bytes(process, addr, mem::size_of::<T>())?;
+    Ok(from_bytes(&buffer))
+}
+
+/// Reads an unsigned 32-bit integer; shorthand for `read_process::<u32>`.
+///
+/// # Returns
+///
+/// A `Result` containing the value, or `DmaError::Read` if it could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let ammo = read_u32(&process, weapon + 0x2C)?;
+/// ```
+pub fn read_u32(process: &VmmProcess, addr: u64) -> Result<u32, DmaError> {
+    read_process(process, addr)
+}
+
+/// Reads an unsigned 64-bit integer; shorthand for `read_process::<u64>`.
+///
+/// # Returns
+///
+/// A `Result` containing the value, or `DmaError::Read` if it could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let entity_list = read_u64(&process, client + 0x1A8)?;
+/// ```
+pub fn read_u64(process: &VmmProcess, addr: u64) -> Result<u64, DmaError> {
+    read_process(process, addr)
+}
+
+/// Reads a signed 32-bit integer; shorthand for `read_process::<i32>`.
+///
+/// # Returns
+///
+/// A `Result` containing the value, or `DmaError::Read` if it could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let health = read_i32(&process, player + 0x100)?;
+/// ```
+pub fn read_i32(process: &VmmProcess, addr: u64) -> Result<i32, DmaError> {
+    read_process(process, addr)
+}
+
+/// Reads a 32-bit float; shorthand for `read_process::<f32>`.
+///
+/// # Returns
+///
+/// A `Result` containing the value, or `DmaError::Read` if it could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let speed = read_f32(&process, player + 0x220)?;
+/// ```
+pub fn read_f32(process: &VmmProcess, addr: u64) -> Result<f32, DmaError> {
+    read_process(process, addr)
+}
+
+/// Reads a 64-bit float; shorthand for `read_process::<f64>`.
+///
+/// # Returns
+///
+/// A `Result` containing the value, or `DmaError::Read` if it could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let game_time = read_f64(&process, globals + 0x10)?;
+/// ```
+pub fn read_f64(process: &VmmProcess, addr: u64) -> Result<f64, DmaError> {
+    read_process(process, addr)
+}
+
+/// Reads a one-byte boolean.
+///
+/// Any non-zero byte is `true`. The byte is read as a `u8` because not every byte
+/// is a valid `bool`, which is why `bool` does not implement [`Pod`].
+///
+/// # Returns
+///
+/// A `Result` containing the value, or `DmaError::Read` if it could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let alive = read_bool(&process, player + 0x3F0)?;
+/// ```
+pub fn read_bool(process: &VmmProcess, addr: u64) -> Result<bool, DmaError> {
+    Ok(read_process::<u8>(process, addr)? != 0)
+}
+
+/// Reads a value of type `T`, treating memory that is not present as a normal outcome.
+///
+/// memprocfs reports a read of unmapped or paged-out memory as a successful call that
+/// returned fewer bytes, and only fails the call itself when the read could not be
+/// issued at all, e.g. because the device is gone or the process handle is invalid.
+/// This function keeps those two cases apart.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address to read from.
+///
+/// # Returns
+///
+/// A `Result` containing `Some(value)` if the value was read, `None` if the memory is
+/// not present right now, or `DmaError::Read` if the read could not be performed.
+///
+/// # Examples
+///
+/// ```ignore
+/// match read_optional::<u64>(&process, entity + 0x30)? {
+///     Some(controller) => println!("Controller: 0x{:X}", controller),
+///     None => continue,
+/// }
+/// ```
+pub fn read_optional<T: Pod>(process: &VmmProcess, addr: u64) -> Result<Option<T>, DmaError> {
+    let len = mem::size_of::<T>();
+    let mut buffer = vec![0u8; len];
+    match process.mem_read_into(addr, 0, &mut buffer) {
+        Ok(bytes_read) if bytes_read == len => Ok(Some(from_bytes(&buffer))),
+        Ok(_) => Ok(None),
+        Err(e) => {
+            log::debug!("Read of {} bytes at 0x{:X} failed: {}", len, addr, e);
+            Err(DmaError::Read { addr, len })
+        }
+    }
+}
+
+/// Reads a value of type `T` at a static offset from a base address.
+///
+/// A null `base` is rejected instead of reading from `0 + offset`, which catches base
+/// lookups that silently failed and returned zero.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `base` - Base address, e.g. of a module or object.
+/// * `offset` - Offset from `base` to read at.
+///
+/// # Returns
+///
+/// A `Result` containing the value read, `DmaError::NullBase` if `base` is zero, or
+/// `DmaError::Read` if the value could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let local_player: u64 = read_offset(&process, client_base, 0x1810F48)?;
+/// ```
+pub fn read_offset<T: Pod>(process: &VmmProcess, base: u64, offset: u64) -> Result<T, DmaError> {
+    if base == 0 {
+        return Err(DmaError::NullBase { offset });
+    }
+    let addr = base.checked_add(offset).ok_or(DmaError::Read {
+        addr: base,
+        len: mem::size_of::<T>(),
+    })?;
+    read_process(process, addr)
+}
+
+/// Reads a contiguous array of `count` values of type `T` in a single read.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address of the first element.
+/// * `count` - Number of elements to read.
+///
+/// # Returns
+///
+/// A `Result` containing the elements, or `DmaError::Read` if fewer than
+/// `count * size_of::<T>()` bytes could be read. An empty `Vec` is returned without
+/// reading if `count` is zero.
+///
+/// # Examples
+///
+/// ```ignore
+/// let entities: Vec<Entity> = read_array(&process, entity_list, 64)?;
+/// ```
+pub fn read_array<T: Pod>(process: &VmmProcess, addr: u64, count: usize) -> Result<Vec<T>, DmaError> {
+    let size = mem::size_of::<T>();
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+    if size == 0 {
+        return Ok(vec![from_bytes(&[]); count]);
+    }
+    let len = count.checked_mul(size).ok_or(DmaError::Read { addr, len: usize::MAX })?;
+    let buffer = read_bytes(process, addr, len)?;
+    Ok(buffer.chunks_exact(size).map(from_bytes).collect())
+}
+
+/// Reads a range of bits, e.g. a packed flag or bitfield member.
+///
+/// Only the bytes containing the requested bits are read, and bit `0` is the least
+/// significant bit of the byte at `addr`, matching how MSVC lays out bitfields. The
+/// range may start at any bit and cross byte boundaries.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address the bit offset is relative to.
+/// * `bit_offset` - Index of the first bit, counted from the least significant bit at `addr`.
+/// * `bit_count` - Number of bits to extract, from `1` to `64`.
+///
+/// # Returns
+///
+/// A `Result` containing the bits shifted down to bit `0`, `DmaError::InvalidArgument`
+/// if `bit_count` is not between `1` and `64`, or `DmaError::Read` if the bytes could
+/// not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// // struct { u32 is_alive : 1; u32 team : 3; } at player + 0x20
+/// let team = read_bits(&process, player + 0x20, 1, 3)?;
+/// ```
+pub fn read_bits(process: &VmmProcess, addr: u64, bit_offset: u32, bit_count: u32) -> Result<u64, DmaError> {
+    if !(1..=64).contains(&bit_count) {
+        return Err(DmaError::InvalidArgument(format!("bit_count {} is not between 1 and 64", bit_count)));
+    }
+    let shift = bit_offset % 8;
+    let len = (shift + bit_count).div_ceil(8) as usize;
+    let bytes = read_bytes(process, addr + (bit_offset / 8) as u64, len)?;
+    Ok(extract_bits(&bytes, shift, bit_count))
+}
+
+/// Extracts `bit_count` bits starting at bit `shift` of the little-endian `bytes`.
+fn extract_bits(bytes: &[u8], shift: u32, bit_count: u32) -> u64 {
+    let mut word = [0u8; 16];
+    word[..bytes.len()].copy_from_slice(bytes);
+    let mask = (1u128 << bit_count) - 1;
+    ((u128::from_le_bytes(word) >> shift) & mask) as u64
+}
+
+/// Reads an array of `count` pointers and dereferences each into a `T`.
+///
+/// The pointer array is read in one read and the pointees in one scatter batch. Null
+/// pointers and pointees that cannot be read are skipped; use [`read_ptr_array_opt`]
+/// to keep the positions of the skipped entries.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `array_addr` - Virtual address of the first pointer.
+/// * `count` - Number of pointers in the array.
+///
+/// # Returns
+///
+/// A `Result` containing the pointees in array order, `DmaError::Read` if the pointer
+/// array could not be read, or `DmaError::Scatter` if the batch failed.
+///
+/// # Examples
+///
+/// ```ignore
+/// let entities: Vec<Entity> = read_ptr_array(&process, entity_list, 64)?;
+/// ```
+pub fn read_ptr_array<T: Pod>(process: &VmmProcess, array_addr: u64, count: usize) -> Result<Vec<T>, DmaError> {
+    Ok(read_ptr_array_opt(process, array_addr, count)?.into_iter().flatten().collect())
+}
+
+/// Reads an array of `count` pointers and dereferences each into an `Option<T>`.
+///
+/// Like [`read_ptr_array`], but the result has one entry per pointer, with `None` for
+/// null pointers and pointees that could not be read.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `array_addr` - Virtual address of the first pointer.
+/// * `count` - Number of pointers in the array.
+///
+/// # Returns
+///
+/// A `Result` containing `count` entries, `DmaError::Read` if the pointer array could
+/// not be read, or `DmaError::Scatter` if the batch failed.
+///
+/// # Examples
+///
+/// ```ignore
+/// for (slot, entity) in read_ptr_array_opt::<Entity>(&process, entity_list, 64)?.iter().enumerate() {
+///     if let Some(entity) = entity {
+///         println!("{}: {}", slot, entity.health);
+///     }
+/// }
+/// ```
+pub fn read_ptr_array_opt<T: Pod>(process: &VmmProcess, array_addr: u64, count: usize) -> Result<Vec<Option<T>>, DmaError> {
+    let pointers: Vec<u64> = read_array(process, array_addr, count)?;
+    if pointers.iter().all(|&pointer| pointer == 0) {
+        return Ok(vec![None; count]);
+    }
+
+    let scatter = ScatterReader::new(process)?;
+    for &pointer in pointers.iter().filter(|&&pointer| pointer != 0) {
+        scatter.prepare(pointer, mem::size_of::<T>())?;
+    }
+    scatter.execute()?;
+    Ok(pointers
+        .into_iter()
+        .map(|pointer| match pointer {
+            0 => None,
+            _ => scatter.read(pointer).ok(),
+        })
+        .collect())
+}
+
+/// Reads exactly `len` bytes from the memory of a process.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address to read from.
+/// * `len` - Number of bytes to read.
+///
+/// # Returns
+///
+/// A `Result` containing the bytes read, or `DmaError::Read` if fewer than `len` bytes
+/// could be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let header = read_bytes(&process, base_address, 0x1000)?;
+/// ```
+pub fn read_bytes(process: &VmmProcess, addr: u64, len: usize) -> Result<Vec<u8>, DmaError> {
+    let mut buffer = vec![0u8; len];
+    read_bytes_into(process, addr, &mut buffer)?;
+    Ok(buffer)
+}
+
+/// Fills a caller-provided buffer with bytes read from the memory of a process.
+///
+/// This avoids allocating a new buffer for every read in hot loops.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address to read from.
+/// * `buffer` - Buffer to fill; its length is the number of bytes read.
+///
+/// # Returns
+///
+/// A `Result` which is `Ok(())` if the whole buffer was filled, or `DmaError::Read` if
+/// fewer bytes could be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut buffer = [0u8; 0x100];
+/// read_bytes_into(&process, entity, &mut buffer)?;
+/// ```
+pub fn read_bytes_into(process: &VmmProcess, addr: u64, buffer: &mut [u8]) -> Result<(), DmaError> {
+    let len = buffer.len();
+    match process.mem_read_into(addr, 0, buffer) {
+        Ok(bytes_read) if bytes_read == len => Ok(()),
+        _ => Err(DmaError::Read { addr, len }),
+    }
+}
+
+/// Reads `len` bytes from the memory of a process with explicit read flags.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address to read from.
+/// * `len` - Number of bytes to read.
+/// * `flags` - Read flags, e.g. `ReadFlags::no_cache()` to force a fresh read.
+///
+/// # Returns
+///
+/// A `Result` containing the bytes read, or `DmaError::Read` if fewer than `len` bytes
+/// could be read. With `ReadFlags::zeropad()` the read only fails if memprocfs rejects
+/// it entirely, and unreadable bytes are returned as zeros.
+///
+/// # Examples
+///
+/// ```ignore
+/// let position = read_bytes_ex(&process, player + 0x10, 12, ReadFlags::no_cache())?;
+/// ```
+pub fn read_bytes_ex(process: &VmmProcess, addr: u64, len: usize, flags: ReadFlags) -> Result<Vec<u8>, DmaError> {
+    let mut buffer = vec![0u8; len];
+    match process.mem_read_into(addr, flags.bits(), &mut buffer) {
+        Ok(bytes_read) if bytes_read == len || flags.contains(ReadFlags::zeropad()) => Ok(buffer),
+        _ => Err(DmaError::Read { addr, len }),
+    }
+}
+
+/// Reinterprets the first `size_of::<T>()` bytes of `bytes` as a `T`.
+pub(crate) fn from_bytes<T: Pod>(bytes: &[u8]) -> T {
+    assert!(bytes.len() >= mem::size_of::<T>());
+    // SAFETY: the slice holds at least `size_of::<T>()` initialized bytes, and `T: Pod`
+    // is valid for any bit pattern they may hold.
+    unsafe { ptr::read_unaligned(bytes.as_ptr() as *const T) }
+}
+
+/// Returns the raw bytes of `value`.
+pub(crate) fn as_bytes<T: Pod>(value: &T) -> &[u8] {
+    // SAFETY: `value` is a valid reference to `size_of::<T>()` bytes, all of which are
+    // initialized because `T: Pod` has no padding.
+    unsafe { slice::from_raw_parts(value as *const T as *const u8, mem::size_of::<T>()) }
+}
+
+/// Writes a value of type `T` to the memory of an already resolved process.
+///
+/// The value is written as its raw in-memory byte representation. The caller is
+/// responsible for making sure the target memory is mapped and writable; writes to
+/// read-only or unmapped pages fail.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address to write to.
+/// * `value` - Reference to the value to write.
+///
+/// # Returns
+///
+/// A `Result` which is `Ok(())` if all `size_of::<T>()` bytes were written, or
+/// `DmaError::Write` if the write failed in full or in part.
+///
+/// # Examples
+///
+/// ```ignore
+/// write(&process, base_address + 0x100, &100i32)?;
+/// ```
+pub fn write<T: Pod>(process: &VmmProcess, addr: u64, value: &T) -> Result<(), DmaError> {
+    let bytes = as_bytes(value);
+    // VMMDLL_MemWrite only reports success when every byte was written.
+    process
+        .mem_write(addr, bytes)
+        .map_err(|_| DmaError::Write { addr, len: bytes.len() })
+}
+
+/// Writes a value of type `T` to the memory of a process identified by its PID.
+///
+/// See [`write`] for details; the caller is responsible for the memory being writable.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `pid` - PID of the process to write to.
+/// * `addr` - Virtual address to write to.
+/// * `value` - Reference to the value to write.
+///
+/// # Returns
+///
+/// A `Result` which is `Ok(())` on success, `DmaError::ProcessNotFound` if the process
+/// could not be found, or `DmaError::Write` if the write failed.
+///
+/// # Examples
+///
+/// ```ignore
+/// write_pid(&vmm, pid, base_address + 0x100, &100i32)?;
+/// ```
+pub fn write_pid<T: Pod>(vmm: &Vmm, pid: u32, addr: u64, value: &T) -> Result<(), DmaError> {
+    let process = vmm
+        .process_from_pid(pid)
+        .map_err(|_| DmaError::ProcessNotFound(format!("with PID {}", pid)))?;
+    write(&process, addr, value)
+}
+
+/// Resolves a multi-level pointer chain.
+///
+/// Reads a `u64` pointer at `base`, then for every offset but the last adds the offset
+/// and dereferences again. The last offset is added to the final pointer without
+/// dereferencing, so `base -> +0x10 -> +0x8 -> +0x30` is expressed as
+/// `read_pointer_chain(&process, base, &[0x10, 0x8, 0x30])`.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `base` - Address of the first pointer.
+/// * `offsets` - Offsets to apply at each level.
+///
+/// # Returns
+///
+/// A `Result` containing the final address, `DmaError::Read` if a pointer could not be
+/// read, or `DmaError::NullPointer` if any pointer in the chain is null.
+///
+/// # Examples
+///
+/// ```ignore
+/// let health_addr = read_pointer_chain(&process, base_address + 0x10, &[0x8, 0x30])?;
+/// let health: i32 = read_process(&process, health_addr)?;
+/// ```
+pub fn read_pointer_chain(process: &VmmProcess, base: u64, offsets: &[u64]) -> Result<u64, DmaError> {
+    read_pointer_chain_with_width(process, base, offsets, PointerWidth::U64)
+}
+
+/// Size of the pointers stored in a process' memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PointerWidth {
+    /// 4-byte pointers, as used by 32-bit (WOW64) processes.
+    U32,
+    /// 8-byte pointers, as used by 64-bit processes.
+    #[default]
+    U64,
+}
+
+impl PointerWidth {
+    /// Returns the pointer width of a process, based on [`is_wow64`].
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `PointerWidth::U32` for WOW64 processes and
+    /// `PointerWidth::U64` otherwise, or `DmaError::ProcessInfo` if it could not be
+    /// determined.
+    pub fn of(process: &VmmProcess) -> Result<Self, DmaError> {
+        Ok(if is_wow64(process)? { PointerWidth::U32 } else { PointerWidth::U64 })
+    }
+
+    /// Returns the size of a pointer in bytes.
+    pub fn size(self) -> usize {
+        match self {
+            PointerWidth::U32 => 4,
+            PointerWidth::U64 => 8,
+        }
+    }
+}
+
+/// Reads a pointer of the given width, zero-extended to a `u64`.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Address of the pointer.
+/// * `width` - Size of the pointer in target memory.
+///
+/// # Returns
+///
+/// A `Result` containing the pointer, or `DmaError::Read` if it could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let local_player = read_pointer(&process, client_base + 0x10F4F4, PointerWidth::U32)?;
+/// ```
+pub fn read_pointer(process: &VmmProcess, addr: u64, width: PointerWidth) -> Result<u64, DmaError> {
+    match width {
+        PointerWidth::U32 => read_process::<u32>(process, addr).map(u64::from),
+        PointerWidth::U64 => read_process::<u64>(process, addr),
+    }
+}
+
+/// Follows a chain of pointers of the given width.
+///
+/// Works like [`read_pointer_chain`], but reads 4-byte pointers for
+/// [`PointerWidth::U32`], which is required for 32-bit (WOW64) targets: reading 8
+/// bytes there picks up the neighbouring field as the high half of the pointer.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `base` - Address of the first pointer.
+/// * `offsets` - Offsets to apply at each level.
+/// * `width` - Size of the pointers in target memory.
+///
+/// # Returns
+///
+/// A `Result` containing the final address, `DmaError::Read` if a pointer could not be
+/// read, or `DmaError::NullPointer` if any pointer in the chain is null.
+///
+/// # Examples
+///
+/// ```ignore
+/// let width = PointerWidth::of(&process)?;
+/// let health_addr = read_pointer_chain_with_width(&process, base_address + 0x10, &[0x8, 0x30], width)?;
+/// ```
+pub fn read_pointer_chain_with_width(process: &VmmProcess, base: u64, offsets: &[u64], width: PointerWidth) -> Result<u64, DmaError> {
+    let mut addr = read_non_null(process, base, width)?;
+    if let Some((last, rest)) = offsets.split_last() {
+        for offset in rest {
+            addr = read_non_null(process, addr.wrapping_add(*offset), width)?;
+        }
+        addr = addr.wrapping_add(*last);
+    }
+    Ok(addr)
+}
+
+/// Reads a pointer at `addr`, failing with `DmaError::NullPointer` if it is null.
+fn read_non_null(process: &VmmProcess, addr: u64, width: PointerWidth) -> Result<u64, DmaError> {
+    match read_pointer(process, addr, width)? {
+        0 => Err(DmaError::NullPointer { addr }),
+        pointer => Ok(pointer),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_bits_reads_single_flags() {
+        assert_eq!(extract_bits(&[0b0000_0100], 2, 1), 1);
+        assert_eq!(extract_bits(&[0b1111_1011], 2, 1), 0);
+    }
+
+    #[test]
+    fn extract_bits_crosses_byte_boundaries() {
+        // Bits 6..10 of 0b0000_0010_1100_0000.
+        assert_eq!(extract_bits(&[0b1100_0000, 0b0000_0010], 6, 4), 0b1011);
+    }
+
+    #[test]
+    fn extract_bits_handles_full_width() {
+        let bytes = [0xF0, 0xDE, 0xBC, 0x9A, 0x78, 0x56, 0x34, 0x12, 0xFF];
+        assert_eq!(extract_bits(&bytes[..8], 0, 64), 0x1234_5678_9ABC_DEF0);
+        assert_eq!(extract_bits(&bytes, 4, 64), 0xF123_4567_89AB_CDEF);
+    }
+}
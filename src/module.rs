@@ -0,0 +1,511 @@
+use crate::pe::{self, IMAGE_DIRECTORY_ENTRY_IMPORT};
+use crate::{pattern_scan, read_bytes, read_offset, read_string, DmaError, Pod};
+use memprocfs::VmmProcess;
+use std::collections::HashMap;
+
+/// Information about a module loaded in a process.
+#[derive(Debug, Clone)]
+pub struct ModuleInfo {
+    /// Module name, e.g. `kernel32.dll`.
+    pub name: String,
+    /// Base address of the module.
+    pub base: u64,
+    /// Size of the module image in bytes.
+    pub size: u64,
+    /// Full path of the module.
+    pub full_path: String,
+}
+
+/// Lists all modules loaded in a process.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+///
+/// # Returns
+///
+/// A `Result` containing a `Vec<ModuleInfo>` for every loaded module, or `DmaError::Map`
+/// if the module map could not be retrieved.
+///
+/// # Examples
+///
+/// ```ignore
+/// for module in list_modules(&process)? {
+///     println!("{} 0x{:X} 0x{:X}", module.name, module.base, module.size);
+/// }
+/// ```
+pub fn list_modules(process: &VmmProcess) -> Result<Vec<ModuleInfo>, DmaError> {
+    let modules = process.map_module(false, false).map_err(|e| DmaError::Map(e.to_string()))?;
+    Ok(modules
+        .into_iter()
+        .map(|module| ModuleInfo {
+            name: module.name,
+            base: module.va_base,
+            size: module.image_size as u64,
+            full_path: module.full_name,
+        })
+        .collect())
+}
+
+/// Looks up a loaded module by name, ignoring case.
+pub(crate) fn find_module(process: &VmmProcess, module_name: &str) -> Result<ModuleInfo, DmaError> {
+    list_modules(process)?
+        .into_iter()
+        .find(|module| module.name.eq_ignore_ascii_case(module_name))
+        .ok_or_else(|| DmaError::ModuleNotFound(module_name.to_string()))
+}
+
+/// Returns the size of a module's image, e.g. to bound a pattern scan.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `module_name` - Name of the module; matched case-insensitively.
+///
+/// # Returns
+///
+/// An `Option<u64>` containing the image size in bytes, or `None` if the module is not
+/// loaded.
+///
+/// # Examples
+///
+/// ```ignore
+/// let size = get_module_size(&process, "client.dll").expect("Module not found");
+/// ```
+pub fn get_module_size(process: &VmmProcess, module_name: &str) -> Option<u64> {
+    get_module_bounds(process, module_name).map(|(_, size)| size)
+}
+
+/// Returns the base address and image size of a module from a single lookup.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `module_name` - Name of the module; matched case-insensitively.
+///
+/// # Returns
+///
+/// An `Option<(u64, u64)>` containing the base address and size in bytes, or `None`
+/// if the module is not loaded.
+///
+/// # Examples
+///
+/// ```ignore
+/// let (base, size) = get_module_bounds(&process, "client.dll").expect("Module not found");
+/// let hit = pattern_scan(&process, base, size, "48 8B 05 ?? ?? ?? ??")?;
+/// ```
+pub fn get_module_bounds(process: &VmmProcess, module_name: &str) -> Option<(u64, u64)> {
+    match find_module(process, module_name) {
+        Ok(module) => Some((module.base, module.size)),
+        Err(e) => {
+            log::warn!("Failed to find {} bounds: {}", module_name, e);
+            None
+        }
+    }
+}
+
+/// Resolves the base addresses of several modules from a single module enumeration.
+///
+/// All bases come from the same snapshot of the module map, and the map is walked
+/// once instead of once per module.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `names` - Names of the modules; matched case-insensitively.
+///
+/// # Returns
+///
+/// A `HashMap` from each requested name, as given, to its base address. Modules that
+/// are not loaded are missing from the map, and the map is empty if the modules could
+/// not be enumerated.
+///
+/// # Examples
+///
+/// ```ignore
+/// let bases = get_module_bases(&process, &["client.dll", "engine.dll", "server.dll"]);
+/// let client = bases["client.dll"];
+/// ```
+pub fn get_module_bases(process: &VmmProcess, names: &[&str]) -> HashMap<String, u64> {
+    let modules = match list_modules(process) {
+        Ok(modules) => modules,
+        Err(e) => {
+            log::warn!("Failed to enumerate modules: {}", e);
+            return HashMap::new();
+        }
+    };
+    names
+        .iter()
+        .filter_map(|name| {
+            let module = modules.iter().find(|module| module.name.eq_ignore_ascii_case(name))?;
+            Some((name.to_string(), module.base))
+        })
+        .collect()
+}
+
+/// Resolves the absolute address of a function or symbol exported by a module.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `module_name` - Name of the exporting module, e.g. `kernel32.dll`.
+/// * `export_name` - Name of the export, e.g. `LoadLibraryA`.
+///
+/// # Returns
+///
+/// A `Result` containing the export address, `DmaError::ModuleNotFound` if the module is
+/// not loaded, or `DmaError::ExportNotFound` if the module does not export the symbol.
+///
+/// # Examples
+///
+/// ```ignore
+/// let load_library = get_export_address(&process, "kernel32.dll", "LoadLibraryA")?;
+/// println!("LoadLibraryA: 0x{:X}", load_library);
+/// ```
+pub fn get_export_address(process: &VmmProcess, module_name: &str, export_name: &str) -> Result<u64, DmaError> {
+    process
+        .get_module_base(module_name)
+        .map_err(|_| DmaError::ModuleNotFound(module_name.to_string()))?;
+    process
+        .get_proc_address(module_name, export_name)
+        .map_err(|_| DmaError::ExportNotFound {
+            module: module_name.to_string(),
+            export: export_name.to_string(),
+        })
+}
+
+/// Information about a function or symbol exported by a module.
+#[derive(Debug, Clone)]
+pub struct ExportInfo {
+    /// Name of the export, empty for exports by ordinal only.
+    pub name: String,
+    /// Ordinal of the export.
+    pub ordinal: u32,
+    /// Absolute address of the export.
+    pub address: u64,
+    /// Target of a forwarded export, e.g. `NTDLL.RtlAllocateHeap`.
+    pub forwarded_to: Option<String>,
+}
+
+/// Lists the functions and symbols exported by a module.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `module_name` - Name of the exporting module, e.g. `kernel32.dll`.
+///
+/// # Returns
+///
+/// A `Result` containing a `Vec<ExportInfo>` for every export, `DmaError::ModuleNotFound`
+/// if the module is not loaded, or `DmaError::Map` if the export table could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// for export in list_exports(&process, "kernel32.dll")? {
+///     println!("{} 0x{:X}", export.name, export.address);
+/// }
+/// ```
+pub fn list_exports(process: &VmmProcess, module_name: &str) -> Result<Vec<ExportInfo>, DmaError> {
+    process
+        .get_module_base(module_name)
+        .map_err(|_| DmaError::ModuleNotFound(module_name.to_string()))?;
+    let exports = process.map_module_eat(module_name).map_err(|e| DmaError::Map(e.to_string()))?;
+    Ok(exports
+        .into_iter()
+        .map(|export| ExportInfo {
+            name: export.function,
+            ordinal: export.ordinal,
+            address: export.va_function,
+            forwarded_to: Some(export.forwarded_function).filter(|forward| !forward.is_empty()),
+        })
+        .collect())
+}
+
+/// Information about a function imported by a module.
+#[derive(Debug, Clone)]
+pub struct ImportInfo {
+    /// Name of the imported function, or `#<ordinal>` for imports by ordinal.
+    pub name: String,
+    /// Name of the module the function is imported from.
+    pub module: String,
+    /// Address of the IAT slot holding the function pointer.
+    pub iat_slot: u64,
+    /// Function address currently stored in the IAT slot.
+    pub address: u64,
+}
+
+/// Lists the functions imported by a module, including their IAT slot addresses.
+///
+/// The memprocfs IAT map does not expose the slot addresses, so the import directory
+/// is walked directly from the module's PE headers in target memory. Comparing
+/// `address` against the real export address reveals IAT hooks.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `module_name` - Name of the importing module.
+///
+/// # Returns
+///
+/// A `Result` containing a `Vec<ImportInfo>` for every import, `DmaError::ModuleNotFound`
+/// if the module is not loaded, or `DmaError::InvalidPe` / `DmaError::Read` if the
+/// import directory could not be parsed.
+///
+/// # Examples
+///
+/// ```ignore
+/// for import in list_imports(&process, "notepad.exe")? {
+///     println!("{}!{} @ 0x{:X}", import.module, import.name, import.iat_slot);
+/// }
+/// ```
+pub fn list_imports(process: &VmmProcess, module_name: &str) -> Result<Vec<ImportInfo>, DmaError> {
+    const NAME_MAX_LEN: usize = 0x100;
+
+    let base = process
+        .get_module_base(module_name)
+        .map_err(|_| DmaError::ModuleNotFound(module_name.to_string()))?;
+    let headers = pe::read_headers(process, base)?;
+    let directory = match headers.data_directories.get(IMAGE_DIRECTORY_ENTRY_IMPORT) {
+        Some(directory) if directory.virtual_address != 0 && directory.size != 0 => *directory,
+        _ => return Ok(Vec::new()),
+    };
+    let descriptors = read_bytes(process, base + directory.virtual_address as u64, directory.size as usize)?;
+    let pointer_size = headers.pointer_size();
+    let mut imports = Vec::new();
+
+    for descriptor in descriptors.chunks_exact(20) {
+        let original_first_thunk = pe::u32_at(descriptor, 0).unwrap_or(0);
+        let name_rva = pe::u32_at(descriptor, 12).unwrap_or(0);
+        let first_thunk = pe::u32_at(descriptor, 16).unwrap_or(0);
+        if name_rva == 0 || first_thunk == 0 {
+            break;
+        }
+
+        let module = read_string(process, base + name_rva as u64, NAME_MAX_LEN)?;
+        let lookup_rva = if original_first_thunk != 0 { original_first_thunk } else { first_thunk };
+        let lookups = pe::read_thunks(process, &headers, base + lookup_rva as u64)?;
+        let iat = base + first_thunk as u64;
+        let slots = read_bytes(process, iat, lookups.len() * pointer_size)?;
+
+        for (index, lookup) in lookups.iter().enumerate() {
+            let name = if lookup & headers.ordinal_flag() != 0 {
+                format!("#{}", lookup & 0xFFFF)
+            } else {
+                read_string(process, base + (lookup & 0x7FFF_FFFF) + 2, NAME_MAX_LEN)?
+            };
+            imports.push(ImportInfo {
+                name,
+                module: module.clone(),
+                iat_slot: iat + (index * pointer_size) as u64,
+                address: headers.thunk_at(&slots, index).unwrap_or(0),
+            });
+        }
+    }
+
+    Ok(imports)
+}
+
+/// `IMAGE_SCN_MEM_EXECUTE` section characteristic.
+pub const IMAGE_SCN_MEM_EXECUTE: u32 = 0x2000_0000;
+/// `IMAGE_SCN_MEM_READ` section characteristic.
+pub const IMAGE_SCN_MEM_READ: u32 = 0x4000_0000;
+/// `IMAGE_SCN_MEM_WRITE` section characteristic.
+pub const IMAGE_SCN_MEM_WRITE: u32 = 0x8000_0000;
+
+/// Information about a section of a module's PE image.
+#[derive(Debug, Clone)]
+pub struct SectionInfo {
+    /// Section name, e.g. `.text`.
+    pub name: String,
+    /// Absolute address of the section in the process.
+    pub address: u64,
+    /// Address of the section relative to the module base.
+    pub virtual_address: u32,
+    /// Size of the section in memory.
+    pub virtual_size: u32,
+    /// Raw `IMAGE_SCN_*` characteristics flags.
+    pub characteristics: u32,
+}
+
+impl SectionInfo {
+    fn new(base: u64, section: pe::SectionHeader) -> Self {
+        Self {
+            name: section.name,
+            address: base + section.virtual_address as u64,
+            virtual_address: section.virtual_address,
+            virtual_size: section.virtual_size,
+            characteristics: section.characteristics,
+        }
+    }
+
+    /// Whether the section is mapped executable.
+    pub fn is_executable(&self) -> bool {
+        self.characteristics & IMAGE_SCN_MEM_EXECUTE != 0
+    }
+
+    /// Whether the section is mapped readable.
+    pub fn is_readable(&self) -> bool {
+        self.characteristics & IMAGE_SCN_MEM_READ != 0
+    }
+
+    /// Whether the section is mapped writable.
+    pub fn is_writable(&self) -> bool {
+        self.characteristics & IMAGE_SCN_MEM_WRITE != 0
+    }
+}
+
+/// Lists the sections of a module by parsing its PE headers from target memory.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `module_name` - Name of the module.
+///
+/// # Returns
+///
+/// A `Result` containing a `Vec<SectionInfo>` for every section, `DmaError::ModuleNotFound`
+/// if the module is not loaded, or `DmaError::InvalidPe` if its headers could not be parsed.
+///
+/// # Examples
+///
+/// ```ignore
+/// for section in get_sections(&process, "client.dll")? {
+///     println!("{} 0x{:X} 0x{:X}", section.name, section.address, section.virtual_size);
+/// }
+/// ```
+pub fn get_sections(process: &VmmProcess, module_name: &str) -> Result<Vec<SectionInfo>, DmaError> {
+    let base = process
+        .get_module_base(module_name)
+        .map_err(|_| DmaError::ModuleNotFound(module_name.to_string()))?;
+    let headers = pe::read_headers(process, base)?;
+    Ok(headers
+        .sections
+        .into_iter()
+        .map(|section| SectionInfo::new(base, section))
+        .collect())
+}
+
+/// A handle to a module loaded in a process, with its base and size resolved once.
+///
+/// Every method works relative to the cached base, so no module lookup is repeated.
+/// The handle does not notice the module being unloaded; look it up again after the
+/// target reloads it.
+///
+/// # Examples
+///
+/// ```ignore
+/// let client = process.module("client.dll")?;
+/// let hit = client.scan("48 8B 05 ?? ?? ?? ??")?.expect("Pattern not found");
+/// let text = client.section(".text")?;
+/// let health: i32 = client.read(0x100)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct Module<'a> {
+    process: VmmProcess<'a>,
+    info: ModuleInfo,
+}
+
+impl<'a> Module<'a> {
+    /// Looks up a loaded module by name, ignoring case.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - Reference to a `VmmProcess` instance representing the target process.
+    /// * `module_name` - Name of the module, e.g. `client.dll`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `Module`, `DmaError::ModuleNotFound` if the module is not
+    /// loaded, or `DmaError::Map` if the module map could not be retrieved.
+    pub fn find(process: &VmmProcess<'a>, module_name: &str) -> Result<Self, DmaError> {
+        Ok(Self {
+            process: *process,
+            info: find_module(process, module_name)?,
+        })
+    }
+
+    /// Returns the module's name, base, size and path.
+    pub fn info(&self) -> &ModuleInfo {
+        &self.info
+    }
+
+    /// Returns the base address of the module.
+    pub fn base(&self) -> u64 {
+        self.info.base
+    }
+
+    /// Returns the size of the module image in bytes.
+    pub fn size(&self) -> u64 {
+        self.info.size
+    }
+
+    /// Whether `addr` lies within the module image.
+    pub fn contains(&self, addr: u64) -> bool {
+        addr >= self.info.base && addr - self.info.base < self.info.size
+    }
+
+    /// Scans the whole module image for the first match of a signature; see [`pattern_scan`].
+    pub fn scan(&self, pattern: &str) -> Result<Option<u64>, DmaError> {
+        pattern_scan(&self.process, self.info.base, self.info.size, pattern)
+    }
+
+    /// Returns a section of the module by name, e.g. `.text`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `SectionInfo`, `DmaError::SectionNotFound` if the module
+    /// has no such section, or `DmaError::InvalidPe` if its headers could not be parsed.
+    pub fn section(&self, section_name: &str) -> Result<SectionInfo, DmaError> {
+        let headers = pe::read_headers(&self.process, self.info.base)?;
+        headers
+            .sections
+            .into_iter()
+            .find(|section| section.name == section_name)
+            .map(|section| SectionInfo::new(self.info.base, section))
+            .ok_or_else(|| DmaError::SectionNotFound {
+                module: self.info.name.clone(),
+                section: section_name.to_string(),
+            })
+    }
+
+    /// Resolves the absolute address of a function or symbol exported by the module.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the export address, or `DmaError::ExportNotFound` if the
+    /// module does not export the symbol.
+    pub fn export(&self, export_name: &str) -> Result<u64, DmaError> {
+        self.process
+            .get_proc_address(&self.info.name, export_name)
+            .map_err(|_| DmaError::ExportNotFound {
+                module: self.info.name.clone(),
+                export: export_name.to_string(),
+            })
+    }
+
+    /// Reads a value of type `T` at `offset` from the module base.
+    pub fn read<T: Pod>(&self, offset: u64) -> Result<T, DmaError> {
+        read_offset(&self.process, self.info.base, offset)
+    }
+}
+
+/// Extension methods on `VmmProcess` for the crate's process-level helpers.
+///
+/// # Examples
+///
+/// ```ignore
+/// use dmalibrary::ProcessExt;
+///
+/// let client = process.module("client.dll")?;
+/// ```
+pub trait ProcessExt<'a> {
+    /// Looks up a loaded module by name, ignoring case; see [`Module::find`].
+    fn module(&self, module_name: &str) -> Result<Module<'a>, DmaError>;
+}
+
+impl<'a> ProcessExt<'a> for VmmProcess<'a> {
+    fn module(&self, module_name: &str) -> Result<Module<'a>, DmaError> {
+        Module::find(self, module_name)
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{read_offset, read_pointer_chain, read_process, DmaError, Pod};
+use memprocfs::VmmProcess;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Named offsets and pointer chains loaded from a file, so they can be updated after a
+/// game patch without recompiling.
+///
+/// The file uses a small subset of TOML. Every entry is either a single offset or an
+/// array describing a pointer chain, and `[section]` headers prefix the names of the
+/// entries below them:
+///
+/// ```toml
+/// # client.dll
+/// entity_list = 0x1810F48
+///
+/// [local_player]
+/// # *(base + 0x1810F48) + 0x100
+/// health = [0x1810F48, 0x100]
+/// # *(*(base + 0x1810F48) + 0x30) + 0x10
+/// position = [0x1810F48, 0x30, 0x10]
+/// ```
+///
+/// A single offset is added to the base. For a chain, the pointer at `base + chain[0]`
+/// is read, every further element but the last is added and dereferenced again, and
+/// the last element is added to the final pointer, as in [`read_pointer_chain`].
+/// Offsets may be decimal or `0x` hexadecimal and may contain `_` separators.
+///
+/// # Examples
+///
+/// ```ignore
+/// let offsets = OffsetTable::from_file(Path::new("offsets.toml"))?;
+/// let health: i32 = offsets.read(&process, client_base, "local_player.health")?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OffsetTable {
+    entries: HashMap<String, Vec<u64>>,
+}
+
+impl OffsetTable {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a table from a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the offsets file.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the table, `DmaError::Io` if the file could not be read, or
+    /// `DmaError::Parse` if it is malformed.
+    pub fn from_file(path: &Path) -> Result<Self, DmaError> {
+        let text = fs::read_to_string(path).map_err(|e| DmaError::Io(format!("{}: {}", path.display(), e)))?;
+        Self::parse(&text).map_err(|e| match e {
+            DmaError::Parse(reason) => DmaError::Parse(format!("{}: {}", path.display(), reason)),
+            e => e,
+        })
+    }
+
+    /// Parses a table from the contents of an offsets file.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the table, or `DmaError::Parse` naming the first malformed
+    /// line.
+    pub fn parse(text: &str) -> Result<Self, DmaError> {
+        let mut table = Self::new();
+        let mut section = String::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let error = |reason: &str| DmaError::Parse(format!("line {}: {}", index + 1, reason));
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[') {
+                let name = name.strip_suffix(']').ok_or_else(|| error("unterminated section header"))?.trim();
+                if name.is_empty() {
+                    return Err(error("empty section name"));
+                }
+                section = format!("{}.", name);
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or_else(|| error("expected `name = offset`"))?;
+            let key = key.trim();
+            if key.is_empty() {
+                return Err(error("empty name"));
+            }
+            let value = value.trim();
+            let chain = match value.strip_prefix('[') {
+                Some(array) => {
+                    let array = array.strip_suffix(']').ok_or_else(|| error("unterminated array"))?;
+                    array
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|element| !element.is_empty())
+                        .map(|element| parse_offset(element).ok_or_else(|| error(&format!("invalid offset `{}`", element))))
+                        .collect::<Result<Vec<_>, _>>()?
+                }
+                None => vec![parse_offset(value).ok_or_else(|| error(&format!("invalid offset `{}`", value)))?],
+            };
+            if chain.is_empty() {
+                return Err(error("empty pointer chain"));
+            }
+            table.entries.insert(format!("{}{}", section, key), chain);
+        }
+
+        Ok(table)
+    }
+
+    /// Adds or replaces an entry; see the type documentation for how `chain` is followed.
+    pub fn insert(&mut self, name: &str, chain: Vec<u64>) {
+        self.entries.insert(name.to_string(), chain);
+    }
+
+    /// Returns the offsets of an entry, or `None` if there is no entry with that name.
+    pub fn get(&self, name: &str) -> Option<&[u64]> {
+        self.entries.get(name).map(Vec::as_slice)
+    }
+
+    /// Resolves an entry to an address by following its pointer chain from `base`.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - Reference to a `VmmProcess` instance representing the target process.
+    /// * `base` - Base address the entry is relative to, usually a module base.
+    /// * `name` - Name of the entry, e.g. `local_player.health`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the address, `DmaError::InvalidArgument` if there is no such
+    /// entry, or the errors of [`read_pointer_chain`].
+    pub fn address(&self, process: &VmmProcess, base: u64, name: &str) -> Result<u64, DmaError> {
+        let chain = self
+            .get(name)
+            .ok_or_else(|| DmaError::InvalidArgument(format!("no offset named {}", name)))?;
+        match chain {
+            [offset] => Ok(base.wrapping_add(*offset)),
+            [first, rest @ ..] => read_pointer_chain(process, base.wrapping_add(*first), rest),
+            [] => Err(DmaError::InvalidArgument(format!("offset {} is empty", name))),
+        }
+    }
+
+    /// Reads a value of type `T` at the address of an entry; see [`address`](OffsetTable::address).
+    pub fn read<T: Pod>(&self, process: &VmmProcess, base: u64, name: &str) -> Result<T, DmaError> {
+        match self.get(name) {
+            Some([offset]) => read_offset(process, base, *offset),
+            _ => read_process(process, self.address(process, base, name)?),
+        }
+    }
+}
+
+/// Parses a decimal or `0x` hexadecimal offset, ignoring `_` separators.
+fn parse_offset(text: &str) -> Option<u64> {
+    let digits = text.replace('_', "");
+    match digits.strip_prefix("0x").or_else(|| digits.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => digits.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_offsets_chains_and_sections() {
+        let table = OffsetTable::parse(
+            "# client.dll\nentity_list = 0x1810F48\n\n[local_player]\nhealth = [0x1810F48, 0x100] # hp\nposition = [ 0x1810_F48, 48, 0x10, ]\n",
+        )
+        .unwrap();
+        assert_eq!(table.get("entity_list"), Some(&[0x1810F48][..]));
+        assert_eq!(table.get("local_player.health"), Some(&[0x1810F48, 0x100][..]));
+        assert_eq!(table.get("local_player.position"), Some(&[0x1810F48, 48, 0x10][..]));
+        assert_eq!(table.get("health"), None);
+    }
+
+    #[test]
+    fn reports_the_malformed_line() {
+        let error = OffsetTable::parse("a = 0x10\nb = 0xZZ\n").unwrap_err();
+        assert!(matches!(error, DmaError::Parse(reason) if reason.starts_with("line 2:")));
+        assert!(OffsetTable::parse("a = [0x10").is_err());
+        assert!(OffsetTable::parse("a = []").is_err());
+        assert!(OffsetTable::parse("[players").is_err());
+        assert!(OffsetTable::parse("just a name").is_err());
+    }
+}
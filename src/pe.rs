@@ -0,0 +1,266 @@
+use crate::DmaError;
+use memprocfs::{VmmProcess, FLAG_ZEROPAD_ON_FAIL};
+
+/// Number of bytes read from the start of an image to parse its headers.
+const HEADER_READ_SIZE: usize = 0x1000;
+
+pub(crate) const IMAGE_DIRECTORY_ENTRY_IMPORT: usize = 1;
+
+/// A PE data directory entry.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DataDirectory {
+    pub virtual_address: u32,
+    pub size: u32,
+}
+
+/// A PE section header.
+#[derive(Debug, Clone)]
+pub(crate) struct SectionHeader {
+    /// Offset of the section header from the start of the image.
+    pub header_offset: usize,
+    pub name: String,
+    pub virtual_size: u32,
+    pub virtual_address: u32,
+    pub characteristics: u32,
+}
+
+/// The parts of a PE image's headers used by this crate.
+#[derive(Debug, Clone)]
+pub(crate) struct PeHeaders {
+    /// `true` for PE32+ (64-bit) images, `false` for PE32 images.
+    pub is_64: bool,
+    /// Offset of the optional header from the start of the image.
+    pub optional_header_offset: usize,
+    pub data_directories: Vec<DataDirectory>,
+    pub sections: Vec<SectionHeader>,
+}
+
+impl PeHeaders {
+    /// Size in bytes of a pointer or thunk in this image.
+    pub fn pointer_size(&self) -> usize {
+        if self.is_64 {
+            8
+        } else {
+            4
+        }
+    }
+
+    /// Bit marking a thunk as an import by ordinal.
+    pub fn ordinal_flag(&self) -> u64 {
+        if self.is_64 {
+            1 << 63
+        } else {
+            1 << 31
+        }
+    }
+
+    /// Reads the thunk at index `index` of a thunk array in `bytes`.
+    pub fn thunk_at(&self, bytes: &[u8], index: usize) -> Option<u64> {
+        let offset = index.checked_mul(self.pointer_size())?;
+        if self.is_64 {
+            u64_at(bytes, offset)
+        } else {
+            u32_at(bytes, offset).map(u64::from)
+        }
+    }
+}
+
+/// Reads and parses the PE headers of the image mapped at `base`.
+pub(crate) fn read_headers(process: &VmmProcess, base: u64) -> Result<PeHeaders, DmaError> {
+    let bytes = process
+        .mem_read_ex(base, HEADER_READ_SIZE, FLAG_ZEROPAD_ON_FAIL)
+        .map_err(|_| DmaError::Read { addr: base, len: HEADER_READ_SIZE })?;
+    parse_headers(&bytes)
+}
+
+/// Reads a null-terminated thunk array starting at `addr`, excluding the terminator.
+pub(crate) fn read_thunks(process: &VmmProcess, headers: &PeHeaders, addr: u64) -> Result<Vec<u64>, DmaError> {
+    const THUNK_BLOCK_SIZE: usize = 0x200;
+    const MAX_THUNKS: usize = 0x10000;
+
+    let mut thunks = Vec::new();
+    while thunks.len() < MAX_THUNKS {
+        let block_addr = addr + (thunks.len() * headers.pointer_size()) as u64;
+        let block = process
+            .mem_read_ex(block_addr, THUNK_BLOCK_SIZE, FLAG_ZEROPAD_ON_FAIL)
+            .map_err(|_| DmaError::Read { addr: block_addr, len: THUNK_BLOCK_SIZE })?;
+        for index in 0..THUNK_BLOCK_SIZE / headers.pointer_size() {
+            match headers.thunk_at(&block, index) {
+                Some(0) | None => return Ok(thunks),
+                Some(thunk) => thunks.push(thunk),
+            }
+        }
+    }
+    Ok(thunks)
+}
+
+/// Parses the PE headers at the start of `bytes`.
+pub(crate) fn parse_headers(bytes: &[u8]) -> Result<PeHeaders, DmaError> {
+    if u16_at(bytes, 0) != Some(0x5A4D) {
+        return Err(DmaError::InvalidPe("missing MZ signature".to_string()));
+    }
+    let nt = u32_at(bytes, 0x3C).ok_or_else(|| DmaError::InvalidPe("truncated DOS header".to_string()))? as usize;
+    if u32_at(bytes, nt) != Some(0x4550) {
+        return Err(DmaError::InvalidPe("missing PE signature".to_string()));
+    }
+
+    let optional = nt + 24;
+    let is_64 = match u16_at(bytes, optional) {
+        Some(0x10B) => false,
+        Some(0x20B) => true,
+        _ => return Err(DmaError::InvalidPe("unknown optional header magic".to_string())),
+    };
+    let (count_offset, directories_offset) = if is_64 { (108, 112) } else { (92, 96) };
+    let count = u32_at(bytes, optional + count_offset)
+        .ok_or_else(|| DmaError::InvalidPe("truncated optional header".to_string()))?
+        .min(16) as usize;
+    let data_directories = (0..count)
+        .filter_map(|i| {
+            let entry = optional + directories_offset + i * 8;
+            Some(DataDirectory {
+                virtual_address: u32_at(bytes, entry)?,
+                size: u32_at(bytes, entry + 4)?,
+            })
+        })
+        .collect();
+
+    let section_count = u16_at(bytes, nt + 6).unwrap_or(0) as usize;
+    let optional_size = u16_at(bytes, nt + 20).unwrap_or(0) as usize;
+    let section_table = optional + optional_size;
+    let sections = (0..section_count)
+        .map(|i| parse_section(bytes, section_table + i * 40))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| DmaError::InvalidPe("truncated section table".to_string()))?;
+
+    Ok(PeHeaders {
+        is_64,
+        optional_header_offset: optional,
+        data_directories,
+        sections,
+    })
+}
+
+fn parse_section(bytes: &[u8], offset: usize) -> Option<SectionHeader> {
+    let raw_name = bytes.get(offset..offset + 8)?;
+    let name_len = raw_name.iter().position(|&b| b == 0).unwrap_or(8);
+    Some(SectionHeader {
+        header_offset: offset,
+        name: String::from_utf8_lossy(&raw_name[..name_len]).into_owned(),
+        virtual_size: u32_at(bytes, offset + 8)?,
+        virtual_address: u32_at(bytes, offset + 12)?,
+        characteristics: u32_at(bytes, offset + 36)?,
+    })
+}
+
+/// Rewrites the headers of an image dumped from memory so its file layout matches the
+/// memory layout.
+///
+/// Each section's `PointerToRawData` is set to its `VirtualAddress` and its
+/// `SizeOfRawData` to the distance to the next section, and `FileAlignment` is set to
+/// `SectionAlignment`, so a file offset equals an RVA throughout the dump.
+pub(crate) fn fix_raw_layout(image: &mut [u8]) -> Result<(), DmaError> {
+    const SECTION_ALIGNMENT_OFFSET: usize = 32;
+    const FILE_ALIGNMENT_OFFSET: usize = 36;
+
+    let headers = parse_headers(image)?;
+    let optional = headers.optional_header_offset;
+    let section_alignment = u32_at(image, optional + SECTION_ALIGNMENT_OFFSET)
+        .ok_or_else(|| DmaError::InvalidPe("truncated optional header".to_string()))?;
+    put_u32(image, optional + FILE_ALIGNMENT_OFFSET, section_alignment);
+
+    let image_len = image.len() as u32;
+    for (index, section) in headers.sections.iter().enumerate() {
+        let start = section.virtual_address.min(image_len);
+        let end = headers
+            .sections
+            .get(index + 1)
+            .map_or(image_len, |next| next.virtual_address.min(image_len))
+            .max(start);
+        put_u32(image, section.header_offset + 16, end - start);
+        put_u32(image, section.header_offset + 20, start);
+    }
+    Ok(())
+}
+
+fn put_u32(bytes: &mut [u8], offset: usize, value: u32) {
+    if let Some(slice) = bytes.get_mut(offset..offset + 4) {
+        slice.copy_from_slice(&value.to_le_bytes());
+    }
+}
+
+pub(crate) fn u16_at(bytes: &[u8], offset: usize) -> Option<u16> {
+    let slice = bytes.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_le_bytes([slice[0], slice[1]]))
+}
+
+pub(crate) fn u32_at(bytes: &[u8], offset: usize) -> Option<u32> {
+    let slice = bytes.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_le_bytes(slice.try_into().ok()?))
+}
+
+pub(crate) fn u64_at(bytes: &[u8], offset: usize) -> Option<u64> {
+    let slice = bytes.get(offset..offset.checked_add(8)?)?;
+    Some(u64::from_le_bytes(slice.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NT: usize = 0x40;
+    const OPTIONAL: usize = NT + 24;
+    const SECTION_TABLE: usize = OPTIONAL + 0xF0;
+
+    /// Builds a 64-bit image of `len` bytes whose sections start at the given RVAs.
+    fn image(len: usize, section_rvas: &[u32]) -> Vec<u8> {
+        let mut image = vec![0u8; len];
+        image[0..2].copy_from_slice(b"MZ");
+        put_u32(&mut image, 0x3C, NT as u32);
+        image[NT..NT + 4].copy_from_slice(b"PE\0\0");
+        image[NT + 6..NT + 8].copy_from_slice(&(section_rvas.len() as u16).to_le_bytes());
+        image[NT + 20..NT + 22].copy_from_slice(&0xF0u16.to_le_bytes());
+        image[OPTIONAL..OPTIONAL + 2].copy_from_slice(&0x20Bu16.to_le_bytes());
+        put_u32(&mut image, OPTIONAL + 32, 0x1000);
+        put_u32(&mut image, OPTIONAL + 36, 0x200);
+        for (index, &rva) in section_rvas.iter().enumerate() {
+            let header = SECTION_TABLE + index * 40;
+            image[header..header + 5].copy_from_slice(b".sect");
+            put_u32(&mut image, header + 8, 0x1000);
+            put_u32(&mut image, header + 12, rva);
+            put_u32(&mut image, header + 16, 0x400);
+            put_u32(&mut image, header + 20, 0x400 * (index as u32 + 1));
+        }
+        image
+    }
+
+    /// Returns `(PointerToRawData, SizeOfRawData)` of every section.
+    fn raw_layout(image: &[u8], count: usize) -> Vec<(u32, u32)> {
+        (0..count)
+            .map(|index| {
+                let header = SECTION_TABLE + index * 40;
+                (u32_at(image, header + 20).unwrap(), u32_at(image, header + 16).unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fix_raw_layout_maps_file_offsets_to_rvas() {
+        let mut dump = image(0x4000, &[0x1000, 0x2000]);
+        fix_raw_layout(&mut dump).unwrap();
+        assert_eq!(u32_at(&dump, OPTIONAL + 36), Some(0x1000));
+        // The last section runs to the end of the image.
+        assert_eq!(raw_layout(&dump, 2), vec![(0x1000, 0x1000), (0x2000, 0x2000)]);
+    }
+
+    #[test]
+    fn fix_raw_layout_clamps_sections_past_the_image() {
+        let mut dump = image(0x3800, &[0x1000, 0x2000, 0x5000]);
+        fix_raw_layout(&mut dump).unwrap();
+        assert_eq!(raw_layout(&dump, 3), vec![(0x1000, 0x1000), (0x2000, 0x1800), (0x3800, 0)]);
+    }
+
+    #[test]
+    fn fix_raw_layout_rejects_non_pe_buffers() {
+        assert!(matches!(fix_raw_layout(&mut [0u8; 0x100]), Err(DmaError::InvalidPe(_))));
+    }
+}
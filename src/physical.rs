@@ -0,0 +1,227 @@
+use crate::dump::write_file;
+use crate::memory::as_bytes;
+use crate::region::query_protection_range;
+use crate::{vfs_read_file, write, DmaError, Pod, VFS_MEMORY_MAP};
+use memprocfs::{Vmm, VmmProcess};
+use std::path::Path;
+use std::mem;
+
+/// Size of a page of physical memory.
+const PAGE_SIZE: u64 = 0x1000;
+
+/// Reads exactly `len` bytes of physical memory.
+///
+/// This bypasses the process page tables entirely, which makes it a useful fallback
+/// when CR3 resolution for a process is unreliable.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `pa` - Physical address to read from.
+/// * `len` - Number of bytes to read.
+///
+/// # Returns
+///
+/// A `Result` containing the bytes read, or `DmaError::Read` if fewer than `len` bytes
+/// could be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let page = read_physical(&vmm, 0x1000, 0x1000)?;
+/// ```
+pub fn read_physical(vmm: &Vmm, pa: u64, len: usize) -> Result<Vec<u8>, DmaError> {
+    let mut buffer = vec![0u8; len];
+    match vmm.mem_read_into(pa, 0, &mut buffer) {
+        Ok(bytes_read) if bytes_read == len => Ok(buffer),
+        _ => Err(DmaError::Read { addr: pa, len }),
+    }
+}
+
+/// Writes bytes to physical memory.
+///
+/// The caller is responsible for the physical address being valid; writing to the
+/// wrong page can corrupt arbitrary kernel or process state on the target.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `pa` - Physical address to write to.
+/// * `bytes` - Bytes to write.
+///
+/// # Returns
+///
+/// A `Result` which is `Ok(())` on success, or `DmaError::Write` if the write failed.
+///
+/// # Examples
+///
+/// ```ignore
+/// write_physical(&vmm, pa, &100i32.to_le_bytes())?;
+/// ```
+pub fn write_physical(vmm: &Vmm, pa: u64, bytes: &[u8]) -> Result<(), DmaError> {
+    vmm.mem_write(pa, bytes)
+        .map_err(|_| DmaError::Write { addr: pa, len: bytes.len() })
+}
+
+/// Translates a virtual address of a process to a physical address.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `va` - Virtual address to translate.
+///
+/// # Returns
+///
+/// A `Result` containing the physical address, or `DmaError::Unmapped` if the page is
+/// not mapped or currently paged out.
+///
+/// # Examples
+///
+/// ```ignore
+/// let pa = virt_to_phys(&process, base_address)?;
+/// let bytes = read_physical(&vmm, pa, 0x100)?;
+/// ```
+pub fn virt_to_phys(process: &VmmProcess, va: u64) -> Result<u64, DmaError> {
+    match process.mem_virt2phys(va) {
+        Ok(pa) if pa != 0 => Ok(pa),
+        _ => Err(DmaError::Unmapped { addr: va }),
+    }
+}
+
+/// Writes a value of type `T` to a process after checking that every page of the target
+/// range is writable.
+///
+/// memprocfs cannot change page protections, so a write to a read-only page cannot be
+/// done by making the page writable and restoring it afterwards. Such writes are
+/// rejected instead; [`write_through_physical`] writes the physical pages directly if
+/// patching shared memory is really intended.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address to write to.
+/// * `value` - Reference to the value to write.
+///
+/// # Returns
+///
+/// A `Result` which is `Ok(())` on success, `DmaError::Unsupported` if a page of the
+/// target range is not writable, `DmaError::Unmapped` if a page is not present, or
+/// `DmaError::Write` if the write failed.
+///
+/// # Examples
+///
+/// ```ignore
+/// write_protected(&process, integrity_flag, &0u8)?;
+/// ```
+pub fn write_protected<T: Pod>(process: &VmmProcess, addr: u64, value: &T) -> Result<(), DmaError> {
+    let size = mem::size_of::<T>();
+    if size == 0 {
+        return Ok(());
+    }
+    let protections = query_protection_range(process, addr, size as u64)?;
+    if let Some(page) = protections.iter().position(|protection| !protection.write) {
+        let page_addr = (addr & !(PAGE_SIZE - 1)) + page as u64 * PAGE_SIZE;
+        return Err(DmaError::Unsupported(format!(
+            "page 0x{:X} is read-only and memprocfs cannot change page protection",
+            page_addr.max(addr)
+        )));
+    }
+    write(process, addr, value)
+}
+
+/// Writes a value of type `T` directly to the physical pages backing a virtual address,
+/// regardless of their protection.
+///
+/// Read-only image and copy-on-write pages are usually shared with every process that
+/// maps the same file, so writing them changes the module system-wide, in all of
+/// those processes, until the page is reloaded from disk. Use [`write_protected`]
+/// unless that is intended.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address to write to.
+/// * `value` - Reference to the value to write.
+///
+/// # Returns
+///
+/// A `Result` which is `Ok(())` on success, `DmaError::Unmapped` if a page of the
+/// target range is not present, or `DmaError::Write` if the write failed.
+///
+/// # Examples
+///
+/// ```ignore
+/// write_through_physical(&process, ntdll_stub, &[0xC3u8])?;
+/// ```
+pub fn write_through_physical<T: Pod>(process: &VmmProcess, addr: u64, value: &T) -> Result<(), DmaError> {
+    let bytes = as_bytes(value);
+    let size = bytes.len();
+    let mut written = 0;
+    while written < size {
+        let va = addr + written as u64;
+        let len = ((PAGE_SIZE - va % PAGE_SIZE) as usize).min(size - written);
+        let pa = virt_to_phys(process, va)?;
+        write_physical(process.vmm, pa, &bytes[written..written + len])?;
+        written += len;
+    }
+    Ok(())
+}
+
+/// Reads the physical memory map of the target from the VFS.
+///
+/// The text is in the format accepted by `-memmap`, so it can be saved to a file with
+/// [`save_memory_map`] and passed to [`VmmBuilder::memmap`](crate::VmmBuilder::memmap)
+/// in later sessions.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+///
+/// # Returns
+///
+/// A `Result` containing the memory map, or `DmaError::Vfs` if it could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let vmm = VmmBuilder::new().device("fpga").memmap_auto().build("vmm.dll")?;
+/// println!("{}", read_memory_map(&vmm)?);
+/// ```
+pub fn read_memory_map(vmm: &Vmm) -> Result<String, DmaError> {
+    let memmap = vfs_read_file(vmm, VFS_MEMORY_MAP)?;
+    if memmap.is_empty() {
+        return Err(DmaError::Vfs(format!("{} is empty", VFS_MEMORY_MAP)));
+    }
+    Ok(String::from_utf8_lossy(&memmap).into_owned())
+}
+
+/// Saves the physical memory map of the target to a file for reuse with `-memmap`.
+///
+/// Run this once in a session started with `-memmap auto`, then start later sessions
+/// with [`VmmBuilder::memmap`](crate::VmmBuilder::memmap) pointing at the file.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `out_path` - Path of the file to write.
+///
+/// # Returns
+///
+/// A `Result` which is `Ok(())` on success, `DmaError::Vfs` if the memory map could not
+/// be read, or `DmaError::Io` if the file could not be written.
+///
+/// # Examples
+///
+/// ```ignore
+/// let path = Path::new("memmap.txt");
+/// let vmm = if path.exists() {
+///     VmmBuilder::new().device("fpga").memmap(path).build("vmm.dll")?
+/// } else {
+///     let vmm = VmmBuilder::new().device("fpga").memmap_auto().build("vmm.dll")?;
+///     save_memory_map(&vmm, path)?;
+///     vmm
+/// };
+/// ```
+pub fn save_memory_map(vmm: &Vmm, out_path: &Path) -> Result<(), DmaError> {
+    write_file(out_path, read_memory_map(vmm)?.as_bytes())
+}
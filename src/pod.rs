@@ -0,0 +1,49 @@
+use crate::Pointer;
+
+/// Plain-old-data types that can be copied to and from target memory as raw bytes.
+///
+/// Every read and write of a typed value goes through this bound, since bytes read
+/// from another process can hold any bit pattern. It is implemented for the integer
+/// and floating-point types, for arrays of `Pod` types and for [`Pointer`].
+///
+/// # Safety
+///
+/// Implementing types must be valid for every bit pattern of their size and must not
+/// contain padding bytes. `bool`, `char`, enums, references and `NonZero*` types do not
+/// qualify, and structs only qualify if they are `#[repr(C)]` or
+/// `#[repr(transparent)]`, consist of `Pod` fields and have no implicit padding.
+///
+/// # Examples
+///
+/// ```ignore
+/// #[repr(C)]
+/// #[derive(Clone, Copy)]
+/// struct Vector3 {
+///     x: f32,
+///     y: f32,
+///     z: f32,
+/// }
+///
+/// // SAFETY: three `f32` fields without padding.
+/// unsafe impl Pod for Vector3 {}
+///
+/// let position: Vector3 = read_process(&process, player + 0x1C8)?;
+/// ```
+pub unsafe trait Pod: Copy {}
+
+macro_rules! impl_pod {
+    ($($ty:ty),*) => {
+        $(
+            // SAFETY: primitive numbers are valid for every bit pattern and have no padding.
+            unsafe impl Pod for $ty {}
+        )*
+    };
+}
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+// SAFETY: an array has the size of its elements laid out back to back, without padding.
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+// SAFETY: `Pointer<T>` is a `#[repr(transparent)]` `u64`.
+unsafe impl<T> Pod for Pointer<T> {}
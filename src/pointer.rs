@@ -0,0 +1,125 @@
+use crate::{read_process, write, DmaError, Pod};
+use memprocfs::VmmProcess;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A typed address in a target process.
+///
+/// `Pointer<T>` is a `u64` with a phantom type, so it has the same layout as a 64-bit
+/// pointer and can be used as a field of structs read with
+/// [`read_process`](crate::read_process). Navigating with
+/// [`offset`](Pointer::offset) and [`deref`](Pointer::deref) keeps the pointee types
+/// checked at compile time instead of passing bare offsets around.
+///
+/// # Examples
+///
+/// ```ignore
+/// let entity_list: Pointer<Pointer<Entity>> = Pointer::new(client_base + 0x1810F48);
+/// let first = entity_list.deref(&process)?;
+/// let health: i32 = first.offset::<i32>(0x100).read(&process)?;
+/// ```
+#[repr(transparent)]
+pub struct Pointer<T> {
+    address: u64,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Pointer<T> {
+    /// Creates a pointer to `address`.
+    pub const fn new(address: u64) -> Self {
+        Self {
+            address,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a null pointer.
+    pub const fn null() -> Self {
+        Self::new(0)
+    }
+
+    /// Returns the address the pointer points to.
+    pub const fn address(self) -> u64 {
+        self.address
+    }
+
+    /// Returns whether the pointer is null.
+    pub const fn is_null(self) -> bool {
+        self.address == 0
+    }
+
+    /// Returns a pointer to a field of type `U` at `offset` bytes into the pointee.
+    pub const fn offset<U>(self, offset: u64) -> Pointer<U> {
+        Pointer::new(self.address.wrapping_add(offset))
+    }
+
+    /// Reinterprets the pointer as pointing to a `U`.
+    pub const fn cast<U>(self) -> Pointer<U> {
+        Pointer::new(self.address)
+    }
+}
+
+impl<T: Pod> Pointer<T> {
+    /// Reads the pointee from the target.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - Reference to a `VmmProcess` instance representing the target process.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the value, or `DmaError::Read` if it could not be read.
+    pub fn read(self, process: &VmmProcess) -> Result<T, DmaError> {
+        read_process(process, self.address)
+    }
+
+    /// Writes `value` to the pointee in the target.
+    pub fn write(self, process: &VmmProcess, value: &T) -> Result<(), DmaError> {
+        write(process, self.address, value)
+    }
+}
+
+impl<T> Pointer<Pointer<T>> {
+    /// Reads the pointer stored at this address.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the stored pointer, `DmaError::NullPointer` if it is null,
+    /// or `DmaError::Read` if it could not be read.
+    pub fn deref(self, process: &VmmProcess) -> Result<Pointer<T>, DmaError> {
+        let pointer = self.read(process)?;
+        if pointer.is_null() {
+            return Err(DmaError::NullPointer { addr: self.address });
+        }
+        Ok(pointer)
+    }
+}
+
+// `T` is only a marker, so these are implemented by hand to avoid requiring `T: Clone`.
+impl<T> Clone for Pointer<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Pointer<T> {}
+
+impl<T> PartialEq for Pointer<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.address == other.address
+    }
+}
+
+impl<T> Eq for Pointer<T> {}
+
+impl<T> fmt::Debug for Pointer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Pointer(0x{:X})", self.address)
+    }
+}
+
+impl<T> From<u64> for Pointer<T> {
+    fn from(address: u64) -> Self {
+        Self::new(address)
+    }
+}
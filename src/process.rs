@@ -0,0 +1,696 @@
+use crate::kernel::eprocess_offset;
+use crate::strings::read_unicode_string32;
+use crate::{read_kernel, read_process, read_unicode_string, DmaError, Pod};
+use memprocfs::{Vmm, VmmProcess, VmmProcessInfo};
+use std::time::{self, SystemTime};
+
+/// Summary information about a running process.
+#[derive(Debug, Clone)]
+pub struct ProcessInfo {
+    /// Process ID.
+    pub pid: u32,
+    /// Process name, e.g. `notepad.exe`.
+    pub name: String,
+    /// Parent process ID.
+    pub ppid: u32,
+    /// Whether the process is a 32-bit process running under WOW64.
+    pub is_wow64: bool,
+    /// Kernel address of the process' `EPROCESS` structure.
+    pub eprocess: u64,
+}
+
+impl From<VmmProcessInfo> for ProcessInfo {
+    fn from(info: VmmProcessInfo) -> Self {
+        // `name` is the kernel's 15 character image name; prefer the untruncated one.
+        let name = if info.name_long.is_empty() { info.name } else { info.name_long };
+        Self {
+            pid: info.pid,
+            name,
+            ppid: info.ppid,
+            is_wow64: info.is_wow64,
+            eprocess: info.va_eprocess,
+        }
+    }
+}
+
+/// Lists all running processes.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+///
+/// # Returns
+///
+/// A `Result` containing a `Vec<ProcessInfo>` for every process, or `DmaError::Map` if
+/// the process list could not be retrieved. Processes whose information cannot be read
+/// are skipped.
+///
+/// # Examples
+///
+/// ```ignore
+/// for process in list_processes(&vmm)? {
+///     println!("{:>6} {}", process.pid, process.name);
+/// }
+/// ```
+pub fn list_processes(vmm: &Vmm) -> Result<Vec<ProcessInfo>, DmaError> {
+    let processes = vmm.process_list().map_err(|e| DmaError::Map(e.to_string()))?;
+    Ok(processes
+        .iter()
+        .filter_map(|process| process.info().ok())
+        .map(ProcessInfo::from)
+        .collect())
+}
+
+/// Finds all processes whose name contains a substring, ignoring case.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `substring` - Text to look for in process names, e.g. `"shipping"`.
+///
+/// # Returns
+///
+/// A `Vec<ProcessInfo>` of the matching processes, which is empty if none match or the
+/// process list could not be retrieved.
+///
+/// # Examples
+///
+/// ```ignore
+/// for process in find_processes_matching(&vmm, "-win64-shipping") {
+///     println!("{} {}", process.pid, process.name);
+/// }
+/// ```
+pub fn find_processes_matching(vmm: &Vmm, substring: &str) -> Vec<ProcessInfo> {
+    let substring = substring.to_lowercase();
+    match list_processes(vmm) {
+        Ok(processes) => processes
+            .into_iter()
+            .filter(|process| process.name.to_lowercase().contains(&substring))
+            .collect(),
+        Err(e) => {
+            log::warn!("Failed to list processes: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Finds a process whose full image path ends with the given suffix, ignoring case.
+///
+/// Unlike [`find_process`](crate::find_process), this tells apart processes with the same
+/// executable name started from different directories. Paths are compared as returned
+/// by [`get_process_path`], usually in NT device form, so match on a suffix such as
+/// `Steam\steamapps\common\Game\game.exe` rather than a drive letter. The suffix must
+/// start at a path component boundary, and forward slashes in it are treated as
+/// backslashes.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `path_suffix` - Trailing part of the image path to match.
+///
+/// # Returns
+///
+/// An `Option<ProcessInfo>` for the first matching process, or `None` if no process
+/// matches or the process list could not be retrieved.
+///
+/// # Examples
+///
+/// ```ignore
+/// let game = find_process_by_path(&vmm, "Live/Game/game.exe").expect("Live client not running");
+/// ```
+pub fn find_process_by_path(vmm: &Vmm, path_suffix: &str) -> Option<ProcessInfo> {
+    let suffix = path_suffix.replace('/', "\\").to_lowercase();
+    let processes = match vmm.process_list() {
+        Ok(processes) => processes,
+        Err(e) => {
+            log::warn!("Failed to list processes: {}", e);
+            return None;
+        }
+    };
+    processes
+        .iter()
+        .find(|process| get_process_path(process).is_ok_and(|path| path_ends_with(&path.to_lowercase(), &suffix)))
+        .and_then(|process| process.info().ok())
+        .map(ProcessInfo::from)
+}
+
+/// Checks that `path` ends with `suffix` on a path component boundary, so that `game.exe`
+/// does not match `C:\Games\minigame.exe`.
+fn path_ends_with(path: &str, suffix: &str) -> bool {
+    path.strip_suffix(suffix).is_some_and(|rest| rest.is_empty() || rest.ends_with('\\') || suffix.starts_with('\\'))
+}
+
+/// Checks whether a PID still belongs to a live process.
+///
+/// The lookup is served from memprocfs' process list, which is refreshed in the
+/// background, so a flaky DMA link does not make a live process look dead. A process
+/// that the list still holds but marks as terminated is reported as not alive. If the
+/// process is listed but its information cannot be read, it is assumed to be alive.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `pid` - PID of the process.
+///
+/// # Returns
+///
+/// `true` if the process is running, `false` if it exited or never existed.
+///
+/// # Examples
+///
+/// ```ignore
+/// while is_alive(&vmm, pid) {
+///     // read game state...
+/// }
+/// ```
+pub fn is_alive(vmm: &Vmm, pid: u32) -> bool {
+    match vmm.process_from_pid(pid) {
+        Ok(process) => process.info().map_or(true, |info| info.state == 0),
+        Err(_) => false,
+    }
+}
+
+/// Checks whether a process with the given PID is known to memprocfs.
+///
+/// Unlike [`is_alive`], this also returns `true` for processes that exited but are
+/// still listed, e.g. because a handle to them remains open.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `pid` - PID of the process.
+///
+/// # Returns
+///
+/// `true` if a process with the PID exists, `false` otherwise.
+///
+/// # Examples
+///
+/// ```ignore
+/// if !process_exists(&vmm, saved_pid) {
+///     saved_pid = find_process(&vmm, "game.exe").expect("Process not found");
+/// }
+/// ```
+pub fn process_exists(vmm: &Vmm, pid: u32) -> bool {
+    vmm.process_from_pid(pid).is_ok()
+}
+
+/// Retrieves summary information about a process by its PID.
+///
+/// This is the PID-based counterpart to finding a process by name, for callers that
+/// already know the PID, e.g. from a previous session.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `pid` - PID of the process.
+///
+/// # Returns
+///
+/// An `Option<ProcessInfo>` containing the information if the process exists, or
+/// `None` if it does not or its information could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let info = get_process_info(&vmm, pid).expect("Process not found");
+/// println!("{} (parent {})", info.name, info.ppid);
+/// ```
+pub fn get_process_info(vmm: &Vmm, pid: u32) -> Option<ProcessInfo> {
+    let process = vmm.process_from_pid(pid).ok()?;
+    match process.info() {
+        Ok(info) => Some(ProcessInfo::from(info)),
+        Err(e) => {
+            log::warn!("Failed to get information of PID {}: {}", pid, e);
+            None
+        }
+    }
+}
+
+/// Commonly used fields of a process environment block (PEB).
+#[derive(Debug, Clone, Copy)]
+pub struct PebInfo {
+    /// Address of the PEB that was read.
+    pub address: u64,
+    /// Whether the 32-bit PEB of a WOW64 process was read.
+    pub is_wow64: bool,
+    /// Base address of the process image.
+    pub image_base_address: u64,
+    /// Whether a debugger is attached according to the PEB.
+    pub being_debugged: bool,
+    /// Address of the loader data (`PEB_LDR_DATA`).
+    pub ldr: u64,
+    /// Address of the process parameters (`RTL_USER_PROCESS_PARAMETERS`).
+    pub process_parameters: u64,
+}
+
+/// Leading fields of the native 64-bit PEB.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Peb64 {
+    inherited_address_space: u8,
+    read_image_file_exec_options: u8,
+    being_debugged: u8,
+    bit_field: u8,
+    padding: [u8; 4],
+    mutant: u64,
+    image_base_address: u64,
+    ldr: u64,
+    process_parameters: u64,
+}
+
+// SAFETY: integer fields only, padded explicitly.
+unsafe impl Pod for Peb64 {}
+
+/// Leading fields of the 32-bit PEB of a WOW64 process.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Peb32 {
+    inherited_address_space: u8,
+    read_image_file_exec_options: u8,
+    being_debugged: u8,
+    bit_field: u8,
+    mutant: u32,
+    image_base_address: u32,
+    ldr: u32,
+    process_parameters: u32,
+}
+
+// SAFETY: integer fields only, without padding.
+unsafe impl Pod for Peb32 {}
+
+/// Reads the process environment block (PEB) of a process.
+///
+/// For WOW64 processes the 32-bit PEB is read, since that is the one the 32-bit
+/// image and loader use; otherwise the native 64-bit PEB is read.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+///
+/// # Returns
+///
+/// A `Result` containing the `PebInfo`, `DmaError::ProcessInfo` if the process
+/// information is unavailable or the process has no PEB, or `DmaError::Read` if the
+/// PEB could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let peb = read_peb(&process)?;
+/// println!("Image base: 0x{:X}, debugged: {}", peb.image_base_address, peb.being_debugged);
+/// ```
+pub fn read_peb(process: &VmmProcess) -> Result<PebInfo, DmaError> {
+    let info = process_info(process)?;
+
+    if info.is_wow64 && info.va_peb32 != 0 {
+        let address = info.va_peb32 as u64;
+        let peb: Peb32 = read_process(process, address)?;
+        return Ok(PebInfo {
+            address,
+            is_wow64: true,
+            image_base_address: peb.image_base_address as u64,
+            being_debugged: peb.being_debugged != 0,
+            ldr: peb.ldr as u64,
+            process_parameters: peb.process_parameters as u64,
+        });
+    }
+
+    if info.va_peb == 0 {
+        return Err(DmaError::ProcessInfo(format!("PID {} has no PEB", process.pid)));
+    }
+    let peb: Peb64 = read_process(process, info.va_peb)?;
+    Ok(PebInfo {
+        address: info.va_peb,
+        is_wow64: false,
+        image_base_address: peb.image_base_address,
+        being_debugged: peb.being_debugged != 0,
+        ldr: peb.ldr,
+        process_parameters: peb.process_parameters,
+    })
+}
+
+/// Reads the command line a process was launched with.
+///
+/// The command line is read from `PEB -> ProcessParameters -> CommandLine`, using
+/// the 32-bit structure layouts for WOW64 processes.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+///
+/// # Returns
+///
+/// A `Result` containing the command line, or an error if the PEB or process
+/// parameters could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// println!("Command line: {}", get_command_line(&process)?);
+/// ```
+pub fn get_command_line(process: &VmmProcess) -> Result<String, DmaError> {
+    let peb = read_peb(process)?;
+    if peb.process_parameters == 0 {
+        let field_offset = if peb.is_wow64 { 0x10 } else { 0x20 };
+        return Err(DmaError::NullPointer { addr: peb.address + field_offset });
+    }
+    if peb.is_wow64 {
+        read_unicode_string32(process, peb.process_parameters + 0x40)
+    } else {
+        read_unicode_string(process, peb.process_parameters + 0x70)
+    }
+}
+
+/// Retrieves the full path of a process' image file.
+///
+/// The path recorded by the kernel when the process was created is preferred, since it
+/// cannot be altered from user mode. It is in NT device form, e.g.
+/// `\Device\HarddiskVolume3\Windows\System32\notepad.exe`. If it is unavailable, the
+/// path from the PEB's process parameters (`ImagePathName`, e.g.
+/// `C:\Windows\System32\notepad.exe`) is returned instead, and the fallback is logged
+/// at debug level.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+///
+/// # Returns
+///
+/// A `Result` containing the image path, or `DmaError::ProcessInfo` if neither source
+/// is available.
+///
+/// # Examples
+///
+/// ```ignore
+/// println!("Image: {}", get_process_path(&process)?);
+/// ```
+pub fn get_process_path(process: &VmmProcess) -> Result<String, DmaError> {
+    if let Some(path) = process.get_path_kernel().ok().filter(|path| !path.is_empty()) {
+        return Ok(path);
+    }
+    log::debug!("No kernel image path for PID {}, falling back to the PEB", process.pid);
+    process
+        .get_path_user()
+        .ok()
+        .filter(|path| !path.is_empty())
+        .ok_or_else(|| DmaError::ProcessInfo(format!("PID {} has no image path", process.pid)))
+}
+
+/// Checks whether a process is a 32-bit process running under WOW64.
+///
+/// The flag from memprocfs' process information is used when available. Otherwise the
+/// process is checked for the `wow64.dll` module, which is mapped into every WOW64
+/// process and no native 64-bit one.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+///
+/// # Returns
+///
+/// A `Result` containing `true` for WOW64 processes, or `DmaError::ProcessInfo` if
+/// neither the process information nor the module map could be retrieved.
+///
+/// # Examples
+///
+/// ```ignore
+/// if is_wow64(&process)? {
+///     println!("32-bit process, use PointerWidth::U32");
+/// }
+/// ```
+pub fn is_wow64(process: &VmmProcess) -> Result<bool, DmaError> {
+    if let Ok(info) = process.info() {
+        return Ok(info.is_wow64);
+    }
+    log::debug!("No process information for PID {}, checking for wow64.dll", process.pid);
+    let modules = process
+        .map_module(false, false)
+        .map_err(|e| DmaError::ProcessInfo(format!("PID {}: {}", process.pid, e)))?;
+    Ok(modules.iter().any(|module| module.name.eq_ignore_ascii_case("wow64.dll")))
+}
+
+/// Information about a thread of a process.
+#[derive(Debug, Clone, Copy)]
+pub struct ThreadInfo {
+    /// Thread ID.
+    pub tid: u32,
+    /// Kernel start address of the thread.
+    pub start_address: u64,
+    /// User-mode (Win32) start address, which points into the owning module.
+    pub win32_start_address: u64,
+    /// Address of the thread environment block (TEB).
+    pub teb: u64,
+    /// Whether the thread is currently running on a processor.
+    pub is_running: bool,
+    /// Whether the thread is suspended.
+    pub is_suspended: bool,
+}
+
+/// Lists the threads of a process.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+///
+/// # Returns
+///
+/// A `Result` containing a `Vec<ThreadInfo>` for every thread, or `DmaError::Map` if
+/// the thread map could not be retrieved.
+///
+/// # Examples
+///
+/// ```ignore
+/// for thread in list_threads(&process)? {
+///     println!("{} start=0x{:X} teb=0x{:X}", thread.tid, thread.win32_start_address, thread.teb);
+/// }
+/// ```
+pub fn list_threads(process: &VmmProcess) -> Result<Vec<ThreadInfo>, DmaError> {
+    let threads = process.map_thread().map_err(|e| DmaError::Map(e.to_string()))?;
+    Ok(threads
+        .into_iter()
+        .map(|thread| ThreadInfo {
+            tid: thread.thread_id,
+            start_address: thread.va_start_address,
+            win32_start_address: thread.va_win32_start_address,
+            teb: thread.va_teb,
+            is_running: thread.running != 0,
+            is_suspended: thread.suspend_count != 0,
+        })
+        .collect())
+}
+
+/// Commonly used fields of a thread environment block (TEB).
+#[derive(Debug, Clone, Copy)]
+pub struct TebInfo {
+    /// Address of the TEB.
+    pub base: u64,
+    /// Highest address of the thread's user-mode stack.
+    pub stack_base: u64,
+    /// Lowest committed address of the thread's user-mode stack.
+    pub stack_limit: u64,
+    /// Address of the thread's TLS slot array (`ThreadLocalStoragePointer`).
+    pub tls_pointer: u64,
+}
+
+/// Leading fields of the native 64-bit TEB, up to `ThreadLocalStoragePointer`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Teb64 {
+    exception_list: u64,
+    stack_base: u64,
+    stack_limit: u64,
+    reserved: [u64; 8],
+    tls_pointer: u64,
+}
+
+// SAFETY: integer fields only, without padding.
+unsafe impl Pod for Teb64 {}
+
+/// Reads the native 64-bit thread environment block (TEB) of a thread.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `tid` - Thread ID of a thread in the process.
+///
+/// # Returns
+///
+/// A `Result` containing the `TebInfo`, `DmaError::ThreadNotFound` if the process has
+/// no such thread (for example because it exited), or `DmaError::Read` if the TEB could
+/// not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let teb = read_teb(&process, tid)?;
+/// println!("TLS array: 0x{:X}", teb.tls_pointer);
+/// ```
+pub fn read_teb(process: &VmmProcess, tid: u32) -> Result<TebInfo, DmaError> {
+    let base = list_threads(process)?
+        .into_iter()
+        .find(|thread| thread.tid == tid)
+        .map(|thread| thread.teb)
+        .filter(|&teb| teb != 0)
+        .ok_or(DmaError::ThreadNotFound(tid))?;
+    let teb: Teb64 = read_process(process, base)?;
+    Ok(TebInfo {
+        base,
+        stack_base: teb.stack_base,
+        stack_limit: teb.stack_limit,
+        tls_pointer: teb.tls_pointer,
+    })
+}
+
+/// An open handle in a process' handle table.
+#[derive(Debug, Clone)]
+pub struct HandleInfo {
+    /// Handle value, as used by the process.
+    pub handle: u32,
+    /// Object type name, e.g. `File`, `Key`, `Process` or `Mutant`.
+    pub object_type: String,
+    /// Name of the object where memprocfs can resolve it, e.g. a file path or
+    /// registry key.
+    pub name: Option<String>,
+    /// Kernel address of the object.
+    pub object: u64,
+    /// Access mask the handle was opened with.
+    pub granted_access: u32,
+    /// PID of the process the handle refers to, for `Process` and `Thread` handles.
+    pub target_pid: Option<u32>,
+}
+
+/// Lists the open handles of a process.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+///
+/// # Returns
+///
+/// A `Result` containing a `Vec<HandleInfo>` for every handle, or `DmaError::Map` if
+/// the handle map could not be retrieved.
+///
+/// # Examples
+///
+/// ```ignore
+/// for handle in list_handles(&process)?.iter().filter(|handle| handle.object_type == "File") {
+///     println!("0x{:X} {}", handle.handle, handle.name.as_deref().unwrap_or("?"));
+/// }
+/// ```
+pub fn list_handles(process: &VmmProcess) -> Result<Vec<HandleInfo>, DmaError> {
+    let handles = process.map_handle().map_err(|e| DmaError::Map(e.to_string()))?;
+    Ok(handles
+        .into_iter()
+        .map(|handle| HandleInfo {
+            handle: handle.handle_id,
+            object_type: handle.tp,
+            name: Some(handle.info).filter(|name| !name.is_empty()),
+            object: handle.va_object,
+            granted_access: handle.granted_access,
+            target_pid: Some(handle.handle_pid).filter(|&pid| pid != 0),
+        })
+        .collect())
+}
+
+/// Retrieves the time a process was created, from `EPROCESS.CreateTime`.
+///
+/// The field offset is resolved from the kernel's debug symbols, so symbols must not
+/// be disabled.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+///
+/// # Returns
+///
+/// A `Result` containing the creation time, `DmaError::Symbol` if the offset could not
+/// be resolved, `DmaError::Read` if the `EPROCESS` could not be read, or
+/// `DmaError::ProcessInfo` if the process has no creation time.
+///
+/// # Examples
+///
+/// ```ignore
+/// let created = get_create_time(&process)?;
+/// println!("Running for {:?}", created.elapsed()?);
+/// ```
+pub fn get_create_time(process: &VmmProcess) -> Result<SystemTime, DmaError> {
+    read_eprocess_time(process, "CreateTime")?
+        .ok_or_else(|| DmaError::ProcessInfo(format!("PID {} has no creation time", process.pid)))
+}
+
+/// Retrieves the time a process exited, from `EPROCESS.ExitTime`.
+///
+/// memprocfs keeps terminated processes whose `EPROCESS` has not been freed yet, e.g.
+/// because a handle to them is still open. They are only listed after writing `1` to
+/// `\conf\config_process_show_terminated.txt`, e.g. with [`vfs_write_file`](crate::vfs_write_file).
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+///
+/// # Returns
+///
+/// A `Result` containing the exit time, or `None` if the process is still running.
+/// Errors are the same as for [`get_create_time`].
+///
+/// # Examples
+///
+/// ```ignore
+/// if let Some(exited) = get_exit_time(&process)? {
+///     println!("Exited after {:?}", exited.duration_since(get_create_time(&process)?)?);
+/// }
+/// ```
+pub fn get_exit_time(process: &VmmProcess) -> Result<Option<SystemTime>, DmaError> {
+    read_eprocess_time(process, "ExitTime")
+}
+
+/// Reads a `FILETIME` field of the process' `EPROCESS`, `None` if it is zero.
+fn read_eprocess_time(process: &VmmProcess, field: &str) -> Result<Option<SystemTime>, DmaError> {
+    let eprocess = process_info(process)?.va_eprocess;
+    let offset = eprocess_offset(process.vmm, field)?;
+    let filetime: u64 = read_kernel(process.vmm, eprocess + offset)?;
+    Ok(filetime_to_system_time(filetime))
+}
+
+/// Number of 100 ns `FILETIME` intervals between 1601-01-01 and the Unix epoch.
+const FILETIME_UNIX_EPOCH: u64 = 116_444_736_000_000_000;
+
+/// Converts a Windows `FILETIME` to a `SystemTime`, returning `None` for zero.
+fn filetime_to_system_time(filetime: u64) -> Option<SystemTime> {
+    if filetime == 0 {
+        return None;
+    }
+    let since_1601 = time::Duration::from_nanos(filetime).checked_mul(100)?;
+    let epoch_offset = time::Duration::from_nanos(FILETIME_UNIX_EPOCH).checked_mul(100)?;
+    match since_1601.checked_sub(epoch_offset) {
+        Some(since_epoch) => SystemTime::UNIX_EPOCH.checked_add(since_epoch),
+        None => SystemTime::UNIX_EPOCH.checked_sub(epoch_offset - since_1601),
+    }
+}
+
+/// Retrieves the memprocfs process information, mapping failures to `DmaError`.
+pub(crate) fn process_info(process: &VmmProcess) -> Result<VmmProcessInfo, DmaError> {
+    process
+        .info()
+        .map_err(|e| DmaError::ProcessInfo(format!("PID {}: {}", process.pid, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filetime_to_system_time_converts_from_1601() {
+        let unix_epoch = filetime_to_system_time(FILETIME_UNIX_EPOCH);
+        assert_eq!(unix_epoch, Some(SystemTime::UNIX_EPOCH));
+        // 2024-01-01T00:00:00Z
+        let new_year = filetime_to_system_time(133_485_408_000_000_000).unwrap();
+        assert_eq!(new_year.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs(), 1_704_067_200);
+    }
+
+    #[test]
+    fn filetime_to_system_time_treats_zero_as_unset() {
+        assert_eq!(filetime_to_system_time(0), None);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{DmaError, SharedVmm};
+use memprocfs::{Vmm, CONFIG_OPT_REFRESH_ALL, CONFIG_OPT_REFRESH_FREQ_MEDIUM, CONFIG_OPT_REFRESH_FREQ_MEM, CONFIG_OPT_REFRESH_FREQ_TLB};
+use std::sync::{mpsc, Arc};
+use std::thread::{self, JoinHandle};
+use std::time;
+
+/// Triggers a full refresh of all memprocfs caches.
+///
+/// The process list, module maps, page tables and cached memory are all rebuilt, so
+/// subsequent lookups see processes started and DLLs loaded since the last refresh.
+/// This is expensive; call it after the target loads new modules, not every frame.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+///
+/// # Returns
+///
+/// A `Result` which is `Ok(())` on success, or `DmaError::InvalidConfig` if memprocfs
+/// rejected the refresh.
+///
+/// # Examples
+///
+/// ```ignore
+/// refresh(&vmm)?;
+/// let client = process.get_module_base("client.dll")?;
+/// ```
+pub fn refresh(vmm: &Vmm) -> Result<(), DmaError> {
+    set_refresh(vmm, CONFIG_OPT_REFRESH_ALL)
+}
+
+/// Drops cached memory and page tables without rebuilding the process and module maps.
+///
+/// Use this when reads must reflect the current target memory, e.g. before a scan,
+/// but no new processes or modules need to be discovered.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+///
+/// # Returns
+///
+/// A `Result` which is `Ok(())` on success, or `DmaError::InvalidConfig` if memprocfs
+/// rejected the refresh.
+///
+/// # Examples
+///
+/// ```ignore
+/// refresh_memory_cache(&vmm)?;
+/// let candidates = scan_refine(&process, &candidates, 100i32)?;
+/// ```
+pub fn refresh_memory_cache(vmm: &Vmm) -> Result<(), DmaError> {
+    set_refresh(vmm, CONFIG_OPT_REFRESH_FREQ_MEM)?;
+    set_refresh(vmm, CONFIG_OPT_REFRESH_FREQ_TLB)
+}
+
+/// Periodically refreshes the process list and module maps on a background thread.
+///
+/// Each refresh runs under the exclusive lock of the [`SharedVmm`], so it never
+/// interleaves with reads made through the same wrapper; those simply wait for it to
+/// finish. memprocfs has its own refresh thread unless started with `-norefresh`, so
+/// use this with `-norefresh` to choose the interval yourself. The thread stops when
+/// the refresher is stopped or dropped, and keeps the `SharedVmm` alive until then.
+///
+/// # Examples
+///
+/// ```ignore
+/// let shared = Arc::new(SharedVmm::new(init(vmm_path, &["", "-device", "fpga", "-norefresh"].to_vec())?));
+/// let refresher = BackgroundRefresher::start(&shared, Duration::from_secs(5));
+/// // Helper processes and late-loaded modules now show up within five seconds.
+/// refresher.stop();
+/// ```
+pub struct BackgroundRefresher {
+    stop: Option<mpsc::Sender<()>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl BackgroundRefresher {
+    /// Starts refreshing `shared` every `interval`, with the first refresh after one
+    /// interval.
+    pub fn start(shared: &Arc<SharedVmm>, interval: time::Duration) -> Self {
+        let (stop, stopped) = mpsc::channel();
+        let shared = Arc::clone(shared);
+        let worker = thread::spawn(move || {
+            while let Err(mpsc::RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                if let Err(e) = shared.with_exclusive(|vmm| set_refresh(vmm, CONFIG_OPT_REFRESH_FREQ_MEDIUM)) {
+                    log::warn!("Background refresh failed: {}", e);
+                }
+            }
+        });
+        Self { stop: Some(stop), worker: Some(worker) }
+    }
+
+    /// Returns whether the background thread is still running.
+    pub fn is_running(&self) -> bool {
+        self.worker.as_ref().is_some_and(|worker| !worker.is_finished())
+    }
+
+    /// Stops the background thread, waiting for a refresh in progress to finish.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        // Dropping the sender wakes the thread even if it is between refreshes.
+        self.stop.take();
+        if let Some(worker) = self.worker.take() {
+            if worker.join().is_err() {
+                log::warn!("Background refresh thread panicked");
+            }
+        }
+    }
+}
+
+impl Drop for BackgroundRefresher {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+fn set_refresh(vmm: &Vmm, option: u64) -> Result<(), DmaError> {
+    vmm.set_config(option, 1)
+        .map_err(|e| DmaError::InvalidConfig(format!("refresh 0x{:X} failed: {}", option, e)))
+}
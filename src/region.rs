@@ -0,0 +1,253 @@
+use crate::DmaError;
+use memprocfs::{VmmProcess, VmmProcessMapVadEntry};
+
+/// Size of a page as used by the page table map.
+const PAGE_SIZE: u64 = 0x1000;
+
+/// Access rights of a page or memory region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PageProtection {
+    /// Whether the memory can be read.
+    pub read: bool,
+    /// Whether the memory can be written, including copy-on-write memory.
+    pub write: bool,
+    /// Whether the memory can be executed.
+    pub execute: bool,
+}
+
+impl PageProtection {
+    /// Decodes the 5-bit `MM_*` protection stored in a VAD, ignoring the cache and guard bits.
+    fn from_vad_protection(protection: u32) -> Self {
+        let (read, write, execute) = match protection & 0x7 {
+            1 => (true, false, false),
+            2 => (false, false, true),
+            3 => (true, false, true),
+            4 | 5 => (true, true, false),
+            6 | 7 => (true, true, true),
+            _ => (false, false, false),
+        };
+        Self { read, write, execute }
+    }
+}
+
+/// What backs a memory region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionType {
+    /// A mapped executable image, i.e. a loaded module.
+    Image,
+    /// A mapped view of a file or section object.
+    Mapped,
+    /// Private memory, e.g. heaps, stacks and `VirtualAlloc` allocations.
+    Private,
+}
+
+/// A virtual memory region of a process, as described by its VAD entry.
+#[derive(Debug, Clone)]
+pub struct MemoryRegion {
+    /// Start address of the region.
+    pub base: u64,
+    /// Size of the region in bytes.
+    pub size: u64,
+    /// Protection the region was created with.
+    pub protection: PageProtection,
+    /// What backs the region.
+    pub region_type: RegionType,
+    /// memprocfs' description of the region, e.g. a module path or `HEAP-00`.
+    pub description: String,
+}
+
+impl From<&VmmProcessMapVadEntry> for MemoryRegion {
+    fn from(vad: &VmmProcessMapVadEntry) -> Self {
+        let region_type = if vad.u0 & (1 << 8) != 0 {
+            RegionType::Image
+        } else if is_private(vad) {
+            RegionType::Private
+        } else {
+            RegionType::Mapped
+        };
+        Self {
+            base: vad.va_start,
+            size: vad.va_end - vad.va_start + 1,
+            protection: PageProtection::from_vad_protection((vad.u0 >> 3) & 0x1F),
+            region_type,
+            description: vad.info.clone(),
+        }
+    }
+}
+
+/// Whether a VAD describes private memory rather than an image or mapped view.
+fn is_private(vad: &VmmProcessMapVadEntry) -> bool {
+    vad.u0 & (1 << 8) == 0 && vad.u0 & (1 << 11) != 0
+}
+
+/// Selects memory regions by type and protection, e.g. for [`scan_all_regions`](crate::scan_all_regions).
+///
+/// The default filter matches every region.
+///
+/// # Examples
+///
+/// ```ignore
+/// // Only private regions that are readable, writable and executable.
+/// let filter = RegionFilter {
+///     region_type: Some(RegionType::Private),
+///     protection: PageProtection { read: true, write: true, execute: true },
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RegionFilter {
+    /// Region type to match, or `None` to match every type.
+    pub region_type: Option<RegionType>,
+    /// Access rights a region must have; rights set to `false` are not required.
+    pub protection: PageProtection,
+}
+
+impl RegionFilter {
+    /// Returns whether `region` is selected by the filter.
+    pub fn matches(&self, region: &MemoryRegion) -> bool {
+        let required = self.protection;
+        let actual = region.protection;
+        self.region_type.is_none_or(|region_type| region_type == region.region_type)
+            && (actual.read || !required.read)
+            && (actual.write || !required.write)
+            && (actual.execute || !required.execute)
+    }
+}
+
+/// Lists the committed memory regions of a process by walking its VAD tree.
+///
+/// Private regions that are only reserved are skipped; image and mapped regions are
+/// always reported. Anonymous private executable regions are a common sign of
+/// injected code.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+///
+/// # Returns
+///
+/// A `Result` containing a `Vec<MemoryRegion>` in ascending address order, or
+/// `DmaError::Map` if the VAD map could not be retrieved.
+///
+/// # Examples
+///
+/// ```ignore
+/// for region in list_memory_regions(&process)? {
+///     if region.region_type == RegionType::Private && region.protection.execute {
+///         println!("Suspicious region at 0x{:X} ({} bytes)", region.base, region.size);
+///     }
+/// }
+/// ```
+pub fn list_memory_regions(process: &VmmProcess) -> Result<Vec<MemoryRegion>, DmaError> {
+    Ok(memory_regions(process)?.collect())
+}
+
+/// Iterates over the committed memory regions of a process.
+///
+/// Like [`list_memory_regions`], but regions are converted one at a time as the
+/// iterator is advanced, so filtering a large VAD map does not build a `MemoryRegion`
+/// for every entry up front.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+///
+/// # Returns
+///
+/// A `Result` containing an iterator over the regions in ascending address order, or
+/// `DmaError::Map` if the VAD map could not be retrieved.
+///
+/// # Examples
+///
+/// ```ignore
+/// let executable = memory_regions(&process)?
+///     .filter(|region| region.region_type == RegionType::Private && region.protection.execute);
+/// for region in executable {
+///     println!("0x{:X} ({} bytes)", region.base, region.size);
+/// }
+/// ```
+pub fn memory_regions(process: &VmmProcess) -> Result<impl Iterator<Item = MemoryRegion>, DmaError> {
+    let vads = process.map_vad(true).map_err(|e| DmaError::Map(e.to_string()))?;
+    Ok(vads
+        .into_iter()
+        .filter(|vad| vad.is_mem_commit || vad.commit_charge != 0 || !is_private(vad))
+        .map(|vad| MemoryRegion::from(&vad)))
+}
+
+/// Returns the effective protection of the page containing an address.
+///
+/// The protection is taken from the process' page tables, so it reflects changes made
+/// with `VirtualProtect` after allocation, unlike [`list_memory_regions`]. Use it to
+/// check that a page is writable before writing to it.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address within the page to query.
+///
+/// # Returns
+///
+/// A `Result` containing the `PageProtection`, `DmaError::Unmapped` if the page is not
+/// present in memory, or `DmaError::Map` if the page table map could not be retrieved.
+///
+/// # Examples
+///
+/// ```ignore
+/// if !query_protection(&process, addr)?.write {
+///     return Err("target page is read-only".into());
+/// }
+/// write(&process, addr, &value)?;
+/// ```
+pub fn query_protection(process: &VmmProcess, addr: u64) -> Result<PageProtection, DmaError> {
+    Ok(query_protection_range(process, addr, 1)?[0])
+}
+
+/// Returns the effective protection of every page overlapping `len` bytes at `addr`,
+/// building the page table map only once.
+pub(crate) fn query_protection_range(process: &VmmProcess, addr: u64, len: u64) -> Result<Vec<PageProtection>, DmaError> {
+    let end = addr.checked_add(len.max(1) - 1).ok_or(DmaError::Unmapped { addr })?;
+    let ptes = process.map_pte(false).map_err(|e| DmaError::Map(e.to_string()))?;
+    (addr / PAGE_SIZE..=end / PAGE_SIZE)
+        .map(|page| {
+            let page_addr = (page * PAGE_SIZE).max(addr);
+            ptes.iter()
+                .find(|pte| page_addr >= pte.va_base && page_addr - pte.va_base < pte.page_count * PAGE_SIZE)
+                .map(|pte| PageProtection {
+                    read: pte.is_r,
+                    write: pte.is_w,
+                    execute: pte.is_x,
+                })
+                .ok_or(DmaError::Unmapped { addr: page_addr })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(region_type: RegionType, read: bool, write: bool, execute: bool) -> MemoryRegion {
+        MemoryRegion {
+            base: 0x10000,
+            size: 0x1000,
+            protection: PageProtection { read, write, execute },
+            region_type,
+            description: String::new(),
+        }
+    }
+
+    #[test]
+    fn default_region_filter_matches_everything() {
+        assert!(RegionFilter::default().matches(&region(RegionType::Mapped, false, false, false)));
+    }
+
+    #[test]
+    fn region_filter_requires_type_and_protection() {
+        let filter = RegionFilter {
+            region_type: Some(RegionType::Private),
+            protection: PageProtection { read: true, write: true, execute: true },
+        };
+        assert!(filter.matches(&region(RegionType::Private, true, true, true)));
+        assert!(!filter.matches(&region(RegionType::Private, true, true, false)));
+        assert!(!filter.matches(&region(RegionType::Image, true, true, true)));
+    }
+}
@@ -0,0 +1,267 @@
+use crate::kernel::system_process;
+use crate::{read_bytes, read_process, write, DmaError, Pod};
+use memprocfs::{Vmm, VmmProcess, FLAG_NOCACHE};
+use std::{thread, time};
+
+/// Number of consecutive failures after which a reconnect is attempted by default.
+const DEFAULT_FAILURE_THRESHOLD: usize = 10;
+
+/// A `Vmm` wrapper that re-initializes itself when the device stops responding.
+///
+/// The original path and arguments are kept so the `Vmm` can be recreated when the
+/// FPGA drops. Failed reads and writes alone do not prove that, since bad pointers
+/// fail the same way, so after the failure threshold is reached an uncached read of
+/// the System page tables probes the device, and only a failed probe reconnects. If
+/// the device responds, the attached process is checked instead, and forgotten if it
+/// has exited. The process is tracked by name and re-resolved after a reconnect, or on
+/// the next access if it was not running or has exited.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut vmm = ResilientVmm::new("vmm.dll", &["", "-device", "fpga"])?.with_failure_threshold(5);
+/// vmm.attach("game.exe")?;
+/// loop {
+///     if let Ok(health) = vmm.read::<i32>(player + 0x100) {
+///         println!("Health: {}", health);
+///     }
+/// }
+/// ```
+pub struct ResilientVmm {
+    vmm_path: String,
+    args: Vec<String>,
+    vmm: Option<Vmm<'static>>,
+    process_name: Option<String>,
+    pid: Option<u32>,
+    failures: usize,
+    failure_threshold: usize,
+}
+
+impl ResilientVmm {
+    /// Initializes a `Vmm` and wraps it.
+    ///
+    /// # Arguments
+    ///
+    /// * `vmm_path` - Path to the VMM (Virtual Machine Monitor).
+    /// * `args` - Arguments to pass to the VMM.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the wrapper, or `DmaError::VmmInit` if initialization failed.
+    pub fn new(vmm_path: &str, args: &[&str]) -> Result<Self, DmaError> {
+        let mut resilient = Self {
+            vmm_path: vmm_path.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            vmm: None,
+            process_name: None,
+            pid: None,
+            failures: 0,
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+        };
+        resilient.connect()?;
+        Ok(resilient)
+    }
+
+    /// Sets the number of consecutive failures that triggers a reconnect.
+    pub fn with_failure_threshold(mut self, failure_threshold: usize) -> Self {
+        self.failure_threshold = failure_threshold.max(1);
+        self
+    }
+
+    /// Returns the current `Vmm`, or `DmaError::Disconnected` if the last reconnect failed.
+    pub fn vmm(&self) -> Result<&Vmm<'static>, DmaError> {
+        self.vmm.as_ref().ok_or(DmaError::Disconnected)
+    }
+
+    /// Finds a process by name and uses it for subsequent reads and writes.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the PID, or `DmaError::ProcessNotFound` if it is not running.
+    pub fn attach(&mut self, process_name: &str) -> Result<u32, DmaError> {
+        self.process_name = Some(process_name.to_string());
+        self.pid = None;
+        self.resolve_process()
+    }
+
+    /// Returns the PID of the attached process, if any.
+    pub fn pid(&self) -> Option<u32> {
+        self.pid
+    }
+
+    /// Returns a handle to the attached process.
+    pub fn process(&self) -> Result<VmmProcess<'_>, DmaError> {
+        let pid = self.pid.ok_or_else(|| DmaError::ProcessNotFound("(not attached)".to_string()))?;
+        Ok(VmmProcess { vmm: self.vmm()?, pid })
+    }
+
+    /// Reads a value of type `T` from the attached process.
+    pub fn read<T: Pod>(&mut self, addr: u64) -> Result<T, DmaError> {
+        self.reattach_if_needed();
+        let result = self.process().and_then(|process| read_process(&process, addr));
+        self.track(result)
+    }
+
+    /// Reads exactly `len` bytes from the attached process.
+    pub fn read_bytes(&mut self, addr: u64, len: usize) -> Result<Vec<u8>, DmaError> {
+        self.reattach_if_needed();
+        let result = self.process().and_then(|process| read_bytes(&process, addr, len));
+        self.track(result)
+    }
+
+    /// Writes a value of type `T` to the attached process.
+    pub fn write<T: Pod>(&mut self, addr: u64, value: &T) -> Result<(), DmaError> {
+        self.reattach_if_needed();
+        let result = self.process().and_then(|process| write(&process, addr, value));
+        self.track(result)
+    }
+
+    /// Re-initializes the `Vmm` with the original path and arguments and re-resolves the
+    /// attached process.
+    pub fn reconnect(&mut self) -> Result<(), DmaError> {
+        log::warn!("Reconnecting Vmm after {} consecutive failures", self.failures);
+        self.failures = 0;
+        // Release the old handle first so the device is free to be reopened, and forget
+        // the PID so a failed re-resolve never leaves reads going to a stale process.
+        self.vmm = None;
+        self.pid = None;
+        self.connect()?;
+        if self.process_name.is_some() {
+            self.resolve_process()?;
+        }
+        Ok(())
+    }
+
+    fn connect(&mut self) -> Result<(), DmaError> {
+        let args = self.args.iter().map(String::as_str).collect();
+        let vmm = Vmm::new(&self.vmm_path, &args).map_err(|e| DmaError::VmmInit(e.to_string()))?;
+        self.vmm = Some(vmm);
+        Ok(())
+    }
+
+    fn resolve_process(&mut self) -> Result<u32, DmaError> {
+        let name = self.process_name.clone().unwrap_or_default();
+        let pid = self
+            .vmm()?
+            .process_from_name(&name)
+            .map_err(|_| DmaError::ProcessNotFound(name))?
+            .pid;
+        self.pid = Some(pid);
+        Ok(pid)
+    }
+
+    /// Re-resolves the attached process by name if it was not running at the last attempt.
+    fn reattach_if_needed(&mut self) {
+        if self.pid.is_none() && self.process_name.is_some() && self.vmm.is_some() {
+            let _ = self.resolve_process();
+        }
+    }
+
+    fn track<T>(&mut self, result: Result<T, DmaError>) -> Result<T, DmaError> {
+        match &result {
+            Ok(_) => self.failures = 0,
+            Err(e) if may_be_link_failure(e) => {
+                self.failures += 1;
+                if self.failures >= self.failure_threshold {
+                    if self.device_responds() {
+                        log::debug!("Device responds after {} failures; not reconnecting", self.failures);
+                        self.failures = 0;
+                        self.forget_exited_process();
+                    } else if let Err(e) = self.reconnect() {
+                        log::warn!("Reconnect failed: {}", e);
+                    }
+                }
+            }
+            Err(DmaError::ProcessNotFound(_)) => self.pid = None,
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// Forgets the PID if the attached process has exited, so the next access
+    /// re-resolves it by name, e.g. after the target restarted.
+    fn forget_exited_process(&mut self) {
+        let Some(pid) = self.pid else {
+            return;
+        };
+        if self.vmm().is_ok_and(|vmm| vmm.process_from_pid(pid).is_err()) {
+            log::debug!("Attached process with PID {} has exited", pid);
+            self.pid = None;
+        }
+    }
+
+    /// Probes the device with an uncached read of the System process' top-level page table,
+    /// which is always present in physical memory.
+    fn device_responds(&self) -> bool {
+        let Ok(vmm) = self.vmm() else {
+            return false;
+        };
+        let Ok(info) = system_process(vmm).info() else {
+            return false;
+        };
+        let mut probe = [0u8; 8];
+        vmm.mem_read_into(info.pa_dtb & !0xFFF, FLAG_NOCACHE, &mut probe)
+            .is_ok_and(|bytes_read| bytes_read == probe.len())
+    }
+}
+
+/// Whether an error can be caused by a dead device, rather than e.g. a process that is
+/// not running.
+fn may_be_link_failure(error: &DmaError) -> bool {
+    matches!(
+        error,
+        DmaError::Read { .. } | DmaError::Write { .. } | DmaError::Disconnected | DmaError::VmmInit(_)
+    )
+}
+
+/// Runs a fallible operation until it succeeds or runs out of attempts.
+///
+/// The operation is retried after sleeping `backoff` whenever it fails, which smooths
+/// over the intermittent read failures FPGA devices produce under load.
+///
+/// # Arguments
+///
+/// * `attempts` - Maximum number of times to run `f`; `0` is treated as `1`.
+/// * `backoff` - Time to wait between attempts.
+/// * `f` - Operation to run.
+///
+/// # Returns
+///
+/// The first successful result, or the error of the last attempt.
+///
+/// # Examples
+///
+/// ```ignore
+/// let health: i32 = with_retry(3, Duration::from_millis(5), || read_process(&process, player + 0x100))?;
+/// ```
+pub fn with_retry<F, T>(attempts: usize, backoff: time::Duration, mut f: F) -> Result<T, DmaError>
+where
+    F: FnMut() -> Result<T, DmaError>,
+{
+    let attempts = attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match f() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < attempts => {
+                log::debug!("Attempt {} of {} failed: {}", attempt, attempts, e);
+                attempt += 1;
+                thread::sleep(backoff);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_device_errors_count_as_link_failures() {
+        assert!(may_be_link_failure(&DmaError::Read { addr: 0x1000, len: 4 }));
+        assert!(may_be_link_failure(&DmaError::Disconnected));
+        assert!(!may_be_link_failure(&DmaError::ProcessNotFound("(not attached)".to_string())));
+        assert!(!may_be_link_failure(&DmaError::NullPointer { addr: 0x1000 }));
+    }
+}
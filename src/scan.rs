@@ -0,0 +1,581 @@
+use crate::memory::from_bytes;
+use crate::module::find_module;
+use crate::{get_sections, memory_regions, read_bytes, read_process, DmaError, Pod, RegionFilter, ScatterReader};
+use memprocfs::VmmProcess;
+use std::mem;
+
+/// Size of the pages unreadable parts of a chunk are skipped in.
+const PAGE_SIZE: u64 = 0x1000;
+
+/// Default number of bytes read from the target per scan or dump step.
+const DEFAULT_CHUNK_SIZE: usize = 0x10000;
+
+/// Number of candidate addresses re-read per scatter batch when refining a value scan.
+const REFINE_BATCH_SIZE: usize = 0x1000;
+
+/// Options controlling how scans and dumps read target memory.
+#[derive(Debug, Clone, Copy)]
+pub struct ScanOptions {
+    /// Number of bytes read per step. Larger chunks need fewer round trips and are far
+    /// faster on fast FPGA links (e.g. `0x100000`), while smaller chunks keep every read
+    /// short on slow or unstable setups where large reads time out. Defaults to 64 KiB.
+    pub chunk_size: usize,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+}
+
+impl ScanOptions {
+    /// Chunk size to use, at least one byte.
+    pub(crate) fn step(&self) -> u64 {
+        self.chunk_size.max(1) as u64
+    }
+}
+
+/// Scans a module's memory for the first match of an IDA-style signature.
+///
+/// The pattern is a whitespace-separated list of hex bytes, where `??` (or `?`)
+/// matches any byte. The region is read in chunks, and consecutive chunks overlap by
+/// the pattern length so matches spanning a chunk boundary are found. Pages that
+/// cannot be read are skipped, and a match never includes bytes that were not read.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `module_base` - Base address of the region to scan.
+/// * `module_size` - Size of the region to scan in bytes.
+/// * `pattern` - Signature to search for, e.g. `"48 8B 05 ?? ?? ?? ?? 48 85 C0"`.
+///
+/// # Returns
+///
+/// A `Result` containing the absolute address of the first match, `None` if the pattern
+/// was not found, or `DmaError::InvalidPattern` if the pattern is malformed.
+///
+/// # Examples
+///
+/// ```ignore
+/// if let Some(addr) = pattern_scan(&process, base, size, "48 8B 05 ?? ?? ?? ?? 48 85 C0")? {
+///     println!("Found at 0x{:X}", addr);
+/// }
+/// ```
+pub fn pattern_scan(process: &VmmProcess, module_base: u64, module_size: u64, pattern: &str) -> Result<Option<u64>, DmaError> {
+    pattern_scan_with_options(process, module_base, module_size, pattern, &ScanOptions::default())
+}
+
+/// Scans a module's memory for the first match of an IDA-style signature, with a
+/// configurable chunk size.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `module_base` - Base address of the region to scan.
+/// * `module_size` - Size of the region to scan in bytes.
+/// * `pattern` - Signature to search for; see [`pattern_scan`].
+/// * `options` - Chunk size to read with.
+///
+/// # Returns
+///
+/// A `Result` containing the absolute address of the first match, `None` if the pattern
+/// was not found, or `DmaError::InvalidPattern` if the pattern is malformed.
+///
+/// # Examples
+///
+/// ```ignore
+/// let options = ScanOptions { chunk_size: 0x100000 };
+/// let hit = pattern_scan_with_options(&process, base, size, "48 8B 05 ?? ?? ?? ??", &options)?;
+/// ```
+pub fn pattern_scan_with_options(process: &VmmProcess, module_base: u64, module_size: u64, pattern: &str, options: &ScanOptions) -> Result<Option<u64>, DmaError> {
+    let pattern = parse_pattern(pattern)?;
+    let hits = scan_pattern(|addr, len| read_bytes(process, addr, len), module_base, module_size, &pattern, options.step(), true)?;
+    Ok(hits.into_iter().next())
+}
+
+/// Returns the addresses of the matches of `pattern` between `base` and `base + size`,
+/// stopping at the first one if `first_only` is set.
+///
+/// `read` must return exactly the requested bytes; `DmaError::Read` failures mark
+/// memory as unreadable and any other error aborts the scan.
+fn scan_pattern(mut read: impl FnMut(u64, usize) -> Result<Vec<u8>, DmaError>, base: u64, size: u64, pattern: &[Option<u8>], step: u64, first_only: bool) -> Result<Vec<u64>, DmaError> {
+    let overlap = pattern.len() as u64 - 1;
+    let end = base.saturating_add(size);
+    let mut matches = Vec::new();
+    let mut addr = base;
+
+    while addr < end {
+        let len = (step + overlap).min(end - addr) as usize;
+        let step_end = addr.saturating_add(step);
+        for (run_addr, run) in read_runs(&mut read, addr, len)? {
+            if first_only {
+                if let Some(offset) = find_pattern(&run, pattern) {
+                    return Ok(vec![run_addr + offset as u64]);
+                }
+                continue;
+            }
+            // Only matches starting in this step are reported; the overlap belongs to the next.
+            matches.extend(
+                find_pattern_all(&run, pattern)
+                    .map(|offset| run_addr + offset as u64)
+                    .filter(|&hit| hit < step_end),
+            );
+        }
+        addr = step_end;
+    }
+
+    Ok(matches)
+}
+
+/// Reads `len` bytes at `addr` and returns the runs of consecutive pages that could be
+/// read, so unreadable memory is never scanned as zeros.
+fn read_runs(read: &mut impl FnMut(u64, usize) -> Result<Vec<u8>, DmaError>, addr: u64, len: usize) -> Result<Vec<(u64, Vec<u8>)>, DmaError> {
+    match read(addr, len) {
+        Ok(bytes) => return Ok(vec![(addr, bytes)]),
+        Err(DmaError::Read { .. }) => {}
+        Err(e) => return Err(e),
+    }
+
+    let end = addr + len as u64;
+    let mut runs: Vec<(u64, Vec<u8>)> = Vec::new();
+    let mut page_addr = addr;
+    while page_addr < end {
+        let page_end = ((page_addr / PAGE_SIZE + 1) * PAGE_SIZE).min(end);
+        match read(page_addr, (page_end - page_addr) as usize) {
+            Ok(page) => match runs.last_mut() {
+                Some((run_addr, run)) if *run_addr + run.len() as u64 == page_addr => run.extend_from_slice(&page),
+                _ => runs.push((page_addr, page)),
+            },
+            Err(DmaError::Read { .. }) => {}
+            Err(e) => return Err(e),
+        }
+        page_addr = page_end;
+    }
+    Ok(runs)
+}
+
+/// Scans a single named section of a module for the first match of a signature.
+///
+/// Restricting the scan to e.g. `.text` avoids false positives in data sections and
+/// reads far less memory than scanning the whole image.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `module_name` - Name of the module.
+/// * `section_name` - Name of the section, e.g. `.text`.
+/// * `pattern` - Signature to search for; see [`pattern_scan`].
+///
+/// # Returns
+///
+/// A `Result` containing the absolute address of the first match, `None` if the pattern
+/// was not found, `DmaError::ModuleNotFound` / `DmaError::SectionNotFound` if the module or
+/// section does not exist, or `DmaError::InvalidPattern` if the pattern is malformed.
+///
+/// # Examples
+///
+/// ```ignore
+/// let hit = pattern_scan_section(&process, "client.dll", ".text", "48 8B 05 ?? ?? ?? ??")?;
+/// ```
+pub fn pattern_scan_section(process: &VmmProcess, module_name: &str, section_name: &str, pattern: &str) -> Result<Option<u64>, DmaError> {
+    let section = get_sections(process, module_name)?
+        .into_iter()
+        .find(|section| section.name == section_name)
+        .ok_or_else(|| DmaError::SectionNotFound {
+            module: module_name.to_string(),
+            section: section_name.to_string(),
+        })?;
+    pattern_scan(process, section.address, section.virtual_size as u64, pattern)
+}
+
+/// Resolves the target of a RIP-relative instruction, such as one found by a pattern scan.
+///
+/// The signed 32-bit displacement at `instruction_addr + offset_to_disp` is added to the
+/// address of the next instruction, which is how the CPU computes the operand of e.g.
+/// `mov rax, [rip + disp]` (`48 8B 05 ?? ?? ?? ??`) or `lea rcx, [rip + disp]`.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `instruction_addr` - Address of the instruction.
+/// * `offset_to_disp` - Offset of the displacement within the instruction, e.g. `3`.
+/// * `instruction_len` - Total length of the instruction in bytes, e.g. `7`.
+///
+/// # Returns
+///
+/// A `Result` containing the absolute target address, or `DmaError::Read` if the
+/// displacement could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let hit = pattern_scan(&process, base, size, "48 8B 05 ?? ?? ?? ?? 48 85 C0")?.expect("Pattern not found");
+/// let global = resolve_rip_relative(&process, hit, 3, 7)?;
+/// ```
+pub fn resolve_rip_relative(process: &VmmProcess, instruction_addr: u64, offset_to_disp: u8, instruction_len: u8) -> Result<u64, DmaError> {
+    let disp: i32 = read_process(process, instruction_addr + offset_to_disp as u64)?;
+    Ok((instruction_addr + instruction_len as u64).wrapping_add_signed(disp as i64))
+}
+
+/// Finds a global or data address by scanning a module for an instruction referencing it.
+///
+/// Runs [`pattern_scan`] over the whole module image and resolves the RIP-relative
+/// operand of the match with [`resolve_rip_relative`], which turns a signature for e.g.
+/// `mov rax, [rip + disp]` into the address of the global it loads.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `module_name` - Name of the module to scan, e.g. `client.dll`.
+/// * `pattern` - Signature matching the start of the instruction; see [`pattern_scan`].
+/// * `disp_offset` - Offset of the displacement within the instruction, e.g. `3`.
+/// * `instr_len` - Total length of the instruction in bytes, e.g. `7`.
+///
+/// # Returns
+///
+/// A `Result` containing the resolved address, `DmaError::ModuleNotFound` if the module
+/// is not loaded, `DmaError::PatternNotFound` if the pattern does not match,
+/// `DmaError::InvalidPattern` if it is malformed, or `DmaError::Read` if the
+/// displacement could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let entity_list = find_data_pointer(&process, "client.dll", "48 8B 0D ?? ?? ?? ?? 48 89 7C 24", 3, 7)?;
+/// let local_player = find_data_pointer(&process, "client.dll", "48 8D 05 ?? ?? ?? ?? C3", 3, 7)?;
+/// ```
+pub fn find_data_pointer(process: &VmmProcess, module_name: &str, pattern: &str, disp_offset: u8, instr_len: u8) -> Result<u64, DmaError> {
+    let module = find_module(process, module_name)?;
+    let hit = pattern_scan(process, module.base, module.size, pattern)?.ok_or_else(|| DmaError::PatternNotFound {
+        module: module_name.to_string(),
+        pattern: pattern.to_string(),
+    })?;
+    resolve_rip_relative(process, hit, disp_offset, instr_len)
+}
+
+/// Scans a memory region for every occurrence of a value, Cheat Engine style.
+///
+/// The region is read in chunks that overlap by the size of `T`, so values spanning a
+/// chunk boundary are found. Pages that cannot be read are skipped, so searching for
+/// a zero value does not report addresses in unmapped memory.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `base` - Base address of the region to scan.
+/// * `size` - Size of the region to scan in bytes.
+/// * `needle` - Value to search for.
+/// * `alignment` - Required alignment of matching addresses, e.g. `4`; `0` or `1` allows
+///   any address.
+///
+/// # Returns
+///
+/// A `Result` containing the addresses of all matches in ascending order.
+///
+/// # Examples
+///
+/// ```ignore
+/// let hits = scan_value(&process, base, size, 100i32, 4)?;
+/// println!("{} candidates", hits.len());
+/// ```
+pub fn scan_value<T: Pod + PartialEq>(process: &VmmProcess, base: u64, size: u64, needle: T, alignment: u64) -> Result<Vec<u64>, DmaError> {
+    scan_value_with_options(process, base, size, needle, alignment, &ScanOptions::default())
+}
+
+/// Scans a memory region for every occurrence of a value, with a configurable chunk size.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `base` - Base address of the region to scan.
+/// * `size` - Size of the region to scan in bytes.
+/// * `needle` - Value to search for.
+/// * `alignment` - Required alignment of matching addresses; see [`scan_value`].
+/// * `options` - Chunk size to read with.
+///
+/// # Returns
+///
+/// A `Result` containing the addresses of all matches in ascending order.
+///
+/// # Examples
+///
+/// ```ignore
+/// let options = ScanOptions { chunk_size: 0x100000 };
+/// let hits = scan_value_with_options(&process, heap, heap_size, 100i32, 4, &options)?;
+/// ```
+pub fn scan_value_with_options<T: Pod + PartialEq>(process: &VmmProcess, base: u64, size: u64, needle: T, alignment: u64, options: &ScanOptions) -> Result<Vec<u64>, DmaError> {
+    scan_value_in(|addr, len| read_bytes(process, addr, len), base, size, needle, alignment, options.step())
+}
+
+/// Returns the `alignment`-aligned addresses between `base` and `base + size` that hold
+/// `needle`, reading memory as [`scan_pattern`] does.
+fn scan_value_in<T: Pod + PartialEq>(mut read: impl FnMut(u64, usize) -> Result<Vec<u8>, DmaError>, base: u64, size: u64, needle: T, alignment: u64, step: u64) -> Result<Vec<u64>, DmaError> {
+    let value_size = mem::size_of::<T>() as u64;
+    let mut matches = Vec::new();
+    if value_size == 0 {
+        return Ok(matches);
+    }
+
+    let alignment = alignment.max(1);
+    let end = base.saturating_add(size);
+    let mut addr = base;
+
+    while addr < end {
+        let len = (step + value_size - 1).min(end - addr);
+        if len < value_size {
+            break;
+        }
+        // Only values starting in this step are reported; the overlap belongs to the next.
+        let step_end = (addr + step).min(end - value_size + 1);
+        for (run_addr, run) in read_runs(&mut read, addr, len as usize)? {
+            let run_end = run_addr + run.len() as u64;
+            let mut candidate = run_addr.next_multiple_of(alignment);
+            while candidate < step_end && candidate + value_size <= run_end {
+                let offset = (candidate - run_addr) as usize;
+                if from_bytes::<T>(&run[offset..offset + value_size as usize]) == needle {
+                    matches.push(candidate);
+                }
+                candidate += alignment;
+            }
+        }
+        addr += step;
+    }
+
+    Ok(matches)
+}
+
+/// Re-reads the results of a previous value scan and keeps those that now hold `needle`.
+///
+/// This is the "next scan" step used to narrow down a dynamic address, e.g. after the
+/// player's health changed from 100 to 80. Candidates are re-read in scatter batches,
+/// and candidates that can no longer be read are dropped.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `candidates` - Addresses returned by [`scan_value`] or an earlier `scan_refine`.
+/// * `needle` - Value the remaining addresses must hold.
+///
+/// # Returns
+///
+/// A `Result` containing the still matching addresses in their original order, or
+/// `DmaError::Scatter` if a batch could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let hits = scan_value(&process, base, size, 100i32, 4)?;
+/// // take some damage...
+/// let hits = scan_refine(&process, &hits, 80i32)?;
+/// ```
+pub fn scan_refine<T: Pod + PartialEq>(process: &VmmProcess, candidates: &[u64], needle: T) -> Result<Vec<u64>, DmaError> {
+    let mut matches = Vec::new();
+    let scatter = ScatterReader::new(process)?;
+
+    for batch in candidates.chunks(REFINE_BATCH_SIZE) {
+        for &addr in batch {
+            scatter.prepare(addr, mem::size_of::<T>())?;
+        }
+        scatter.execute()?;
+        matches.extend(
+            batch
+                .iter()
+                .copied()
+                .filter(|&addr| scatter.read::<T>(addr).is_ok_and(|value| value == needle)),
+        );
+        scatter.clear()?;
+    }
+
+    Ok(matches)
+}
+
+/// Scans every committed memory region matching a filter for all matches of a signature.
+///
+/// Regions come from the VAD walk of [`memory_regions`], so anonymous private memory
+/// such as injected code is covered, not just modules. Each region is read in chunks
+/// as in [`pattern_scan`], and pages that cannot be read, such as private memory that
+/// is not resident, are skipped.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `pattern` - Signature to search for; see [`pattern_scan`].
+/// * `filter` - Regions to scan.
+///
+/// # Returns
+///
+/// A `Result` containing the addresses of all matches in ascending order,
+/// `DmaError::InvalidPattern` if the pattern is malformed, or `DmaError::Map` if the VAD
+/// map could not be retrieved.
+///
+/// # Examples
+///
+/// ```ignore
+/// let filter = RegionFilter {
+///     region_type: Some(RegionType::Private),
+///     protection: PageProtection { read: true, write: true, execute: true },
+/// };
+/// for hit in scan_all_regions(&process, "4D 5A 90 00", filter)? {
+///     println!("PE header in RWX memory at 0x{:X}", hit);
+/// }
+/// ```
+pub fn scan_all_regions(process: &VmmProcess, pattern: &str, filter: RegionFilter) -> Result<Vec<u64>, DmaError> {
+    scan_all_regions_with_options(process, pattern, filter, &ScanOptions::default())
+}
+
+/// Scans every committed memory region matching a filter for all matches of a
+/// signature, with a configurable chunk size.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `pattern` - Signature to search for; see [`pattern_scan`].
+/// * `filter` - Regions to scan.
+/// * `options` - Chunk size to read with.
+///
+/// # Returns
+///
+/// A `Result` containing the addresses of all matches in ascending order,
+/// `DmaError::InvalidPattern` if the pattern is malformed, or `DmaError::Map` if the VAD
+/// map could not be retrieved.
+///
+/// # Examples
+///
+/// ```ignore
+/// let options = ScanOptions { chunk_size: 0x100000 };
+/// let hits = scan_all_regions_with_options(&process, "4D 5A 90 00", RegionFilter::default(), &options)?;
+/// ```
+pub fn scan_all_regions_with_options(process: &VmmProcess, pattern: &str, filter: RegionFilter, options: &ScanOptions) -> Result<Vec<u64>, DmaError> {
+    let pattern = parse_pattern(pattern)?;
+    let mut matches = Vec::new();
+    for region in memory_regions(process)?.filter(|region| filter.matches(region)) {
+        let read = |addr, len| read_bytes(process, addr, len);
+        matches.extend(scan_pattern(read, region.base, region.size, &pattern, options.step(), false)?);
+    }
+    Ok(matches)
+}
+
+/// Parses an IDA-style signature into bytes, with `None` marking a wildcard.
+pub(crate) fn parse_pattern(pattern: &str) -> Result<Vec<Option<u8>>, DmaError> {
+    let bytes = pattern
+        .split_whitespace()
+        .map(|token| match token {
+            "?" | "??" => Ok(None),
+            _ if token.len() == 2 => u8::from_str_radix(token, 16)
+                .map(Some)
+                .map_err(|_| DmaError::InvalidPattern(format!("invalid byte '{}'", token))),
+            _ => Err(DmaError::InvalidPattern(format!("invalid byte '{}'", token))),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if bytes.is_empty() {
+        return Err(DmaError::InvalidPattern("pattern is empty".to_string()));
+    }
+    Ok(bytes)
+}
+
+/// Returns the offset of the first match of `pattern` in `haystack`.
+pub(crate) fn find_pattern(haystack: &[u8], pattern: &[Option<u8>]) -> Option<usize> {
+    find_pattern_all(haystack, pattern).next()
+}
+
+/// Returns the offsets of all matches of `pattern` in `haystack`, including overlapping ones.
+fn find_pattern_all<'a>(haystack: &'a [u8], pattern: &'a [Option<u8>]) -> impl Iterator<Item = usize> + 'a {
+    haystack.windows(pattern.len()).enumerate().filter_map(move |(offset, window)| {
+        window
+            .iter()
+            .zip(pattern)
+            .all(|(byte, expected)| expected.is_none_or(|expected| *byte == expected))
+            .then_some(offset)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MemoryReader, MockMemory};
+
+    fn reader(mock: &MockMemory) -> impl FnMut(u64, usize) -> Result<Vec<u8>, DmaError> + '_ {
+        |addr, len| mock.read_memory(1, addr, len)
+    }
+
+    #[test]
+    fn find_pattern_all_reports_overlapping_matches() {
+        let pattern = parse_pattern("AA ?? AA").unwrap();
+        let haystack = [0xAA, 0x01, 0xAA, 0x02, 0xAA, 0xAA];
+        assert_eq!(find_pattern_all(&haystack, &pattern).collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(find_pattern(&haystack, &pattern), Some(0));
+    }
+
+    #[test]
+    fn find_pattern_matches_leading_and_trailing_wildcards() {
+        let haystack = [0x10, 0x48, 0x8B, 0x05, 0x20];
+        assert_eq!(find_pattern(&haystack, &parse_pattern("?? 48 8B").unwrap()), Some(0));
+        assert_eq!(find_pattern(&haystack, &parse_pattern("8B 05 ??").unwrap()), Some(2));
+        assert_eq!(find_pattern(&haystack, &parse_pattern("05 ?? ??").unwrap()), None);
+    }
+
+    #[test]
+    fn scan_pattern_finds_matches_spanning_a_chunk_boundary() {
+        let mut bytes = vec![0xCC; 0x40];
+        bytes[0x1E..0x22].copy_from_slice(&[0x48, 0x8B, 0x05, 0x10]);
+        let mock = MockMemory::new().with_memory(1, 0x1000, bytes);
+        let pattern = parse_pattern("48 8B 05 ??").unwrap();
+        assert_eq!(scan_pattern(reader(&mock), 0x1000, 0x40, &pattern, 0x20, true).unwrap(), vec![0x101E]);
+        assert_eq!(scan_pattern(reader(&mock), 0x1000, 0x40, &pattern, 0x20, false).unwrap(), vec![0x101E]);
+    }
+
+    #[test]
+    fn scan_pattern_reports_overlap_matches_once() {
+        let mock = MockMemory::new().with_memory(1, 0x1000, vec![0xAA; 0x10]);
+        let pattern = parse_pattern("AA AA").unwrap();
+        let hits = scan_pattern(reader(&mock), 0x1000, 0x10, &pattern, 4, false).unwrap();
+        assert_eq!(hits, (0x1000..0x100F).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn scan_pattern_skips_unreadable_pages() {
+        let mock = MockMemory::new()
+            .with_memory(1, 0x10000, vec![0xCC; 0x1000])
+            .with_memory(1, 0x12000, vec![0xCC; 0x1000]);
+        let pattern = parse_pattern("00 00 ?? 00").unwrap();
+        assert!(scan_pattern(reader(&mock), 0x10000, 0x3000, &pattern, 0x10000, false).unwrap().is_empty());
+        let pattern = parse_pattern("CC CC").unwrap();
+        assert_eq!(scan_pattern(reader(&mock), 0x10FFF, 0x1002, &pattern, 0x10000, false).unwrap(), Vec::<u64>::new());
+        assert_eq!(scan_pattern(reader(&mock), 0x11FFE, 0x10, &pattern, 0x10000, true).unwrap(), vec![0x12000]);
+    }
+
+    #[test]
+    fn scan_pattern_reports_errors_other_than_unreadable_memory() {
+        let pattern = parse_pattern("CC").unwrap();
+        let read = |_, _| Err(DmaError::ProcessNotFound("with PID 1".to_string()));
+        assert!(matches!(scan_pattern(read, 0x1000, 0x10, &pattern, 0x10, false), Err(DmaError::ProcessNotFound(_))));
+    }
+
+    #[test]
+    fn scan_value_finds_values_spanning_a_chunk_boundary() {
+        let mut bytes = vec![0; 0x20];
+        bytes[0x0E..0x12].copy_from_slice(&100i32.to_le_bytes());
+        bytes[0x18..0x1C].copy_from_slice(&100i32.to_le_bytes());
+        let mock = MockMemory::new().with_memory(1, 0x1000, bytes);
+        assert_eq!(scan_value_in(reader(&mock), 0x1000, 0x20, 100i32, 1, 0x10).unwrap(), vec![0x100E, 0x1018]);
+        assert_eq!(scan_value_in(reader(&mock), 0x1000, 0x20, 100i32, 4, 0x10).unwrap(), vec![0x1018]);
+    }
+
+    #[test]
+    fn scan_value_skips_unreadable_pages() {
+        let mock = MockMemory::new()
+            .with_memory(1, 0x10000, vec![0xFF; 0x1000])
+            .with_memory(1, 0x12000, vec![0; 8]);
+        let hits = scan_value_in(reader(&mock), 0x10000, 0x2008, 0u32, 4, 0x10000).unwrap();
+        assert_eq!(hits, vec![0x12000, 0x12004]);
+    }
+
+    #[test]
+    fn scan_value_reports_errors_other_than_unreadable_memory() {
+        let read = |_, _| Err(DmaError::ProcessNotFound("with PID 1".to_string()));
+        assert!(matches!(scan_value_in(read, 0x1000, 0x10, 0u32, 4, 0x10), Err(DmaError::ProcessNotFound(_))));
+    }
+}
@@ -0,0 +1,284 @@
+use crate::memory::from_bytes;
+use crate::{DmaError, Pod};
+use memprocfs::{VmmProcess, VmmScatterMemory};
+use std::collections::HashMap;
+use std::mem;
+
+/// Batches many reads from a process into a single DMA operation.
+///
+/// Reads are queued with [`prepare`](ScatterReader::prepare), performed together by
+/// [`execute`](ScatterReader::execute), and then pulled out with
+/// [`read`](ScatterReader::read). The buffers for queued reads are owned by the
+/// underlying memprocfs scatter handle, which lives as long as the `ScatterReader`;
+/// the reader in turn borrows the `VmmProcess` it was created from and cannot
+/// outlive it.
+///
+/// # Examples
+///
+/// ```ignore
+/// let scatter = ScatterReader::new(&process)?;
+/// for entity in &entities {
+///     scatter.prepare(entity.address + 0x10, 12)?;
+/// }
+/// scatter.execute()?;
+/// for entity in &entities {
+///     let position: [f32; 3] = scatter.read(entity.address + 0x10)?;
+/// }
+/// ```
+pub struct ScatterReader<'a> {
+    scatter: VmmScatterMemory<'a>,
+}
+
+impl<'a> ScatterReader<'a> {
+    /// Creates a new `ScatterReader` for the given process.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - Reference to a `VmmProcess` instance representing the target process.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `ScatterReader`, or `DmaError::Scatter` if the scatter
+    /// handle could not be created.
+    pub fn new(process: &'a VmmProcess) -> Result<Self, DmaError> {
+        let scatter = process.mem_scatter(0).map_err(|e| DmaError::Scatter(e.to_string()))?;
+        Ok(Self { scatter })
+    }
+
+    /// Queues a read of `len` bytes at `addr`.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - Virtual address to read from.
+    /// * `len` - Number of bytes to read.
+    pub fn prepare(&self, addr: u64, len: usize) -> Result<(), DmaError> {
+        self.scatter.prepare(addr, len).map_err(|e| DmaError::Scatter(e.to_string()))
+    }
+
+    /// Performs all queued reads in one operation.
+    ///
+    /// Calling `execute` again re-reads the same ranges, which is useful for reading
+    /// the same set of addresses every frame.
+    pub fn execute(&self) -> Result<(), DmaError> {
+        self.scatter.execute().map_err(|e| DmaError::Scatter(e.to_string()))
+    }
+
+    /// Returns a value of type `T` read at `addr` by the last [`execute`](ScatterReader::execute).
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - Virtual address that was previously prepared.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the value, or `DmaError::Read` if the range was not
+    /// prepared or could not be fully read.
+    pub fn read<T: Pod>(&self, addr: u64) -> Result<T, DmaError> {
+        let bytes = self.read_bytes(addr, mem::size_of::<T>())?;
+        Ok(from_bytes(&bytes))
+    }
+
+    /// Returns `len` bytes read at `addr` by the last [`execute`](ScatterReader::execute).
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - Virtual address that was previously prepared.
+    /// * `len` - Number of bytes to return.
+    pub fn read_bytes(&self, addr: u64, len: usize) -> Result<Vec<u8>, DmaError> {
+        let mut buffer = vec![0u8; len];
+        match self.scatter.read_into(addr, &mut buffer) {
+            Ok(bytes_read) if bytes_read == len => Ok(buffer),
+            _ => Err(DmaError::Read { addr, len }),
+        }
+    }
+
+    /// Removes all queued reads so the reader can be reused.
+    pub fn clear(&self) -> Result<(), DmaError> {
+        self.scatter.clear().map_err(|e| DmaError::Scatter(e.to_string()))
+    }
+}
+
+/// Batches many writes to a process into a single DMA operation.
+///
+/// Writes are queued with [`prepare`](ScatterWriter::prepare) and performed together by
+/// [`execute`](ScatterWriter::execute). The bytes are copied when queued, so the source
+/// buffers do not need to outlive the call. memprocfs does not report the outcome of
+/// individual writes in a batch; `execute` only fails if the batch as a whole fails.
+///
+/// # Examples
+///
+/// ```ignore
+/// let scatter = ScatterWriter::new(&process)?;
+/// scatter.prepare_value(player + 0x10, &frozen_position)?;
+/// scatter.prepare_value(player + 0x100, &100i32)?;
+/// scatter.execute()?;
+/// ```
+pub struct ScatterWriter<'a> {
+    scatter: VmmScatterMemory<'a>,
+}
+
+impl<'a> ScatterWriter<'a> {
+    /// Creates a new `ScatterWriter` for the given process.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - Reference to a `VmmProcess` instance representing the target process.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `ScatterWriter`, or `DmaError::Scatter` if the scatter
+    /// handle could not be created.
+    pub fn new(process: &'a VmmProcess) -> Result<Self, DmaError> {
+        let scatter = process.mem_scatter(0).map_err(|e| DmaError::Scatter(e.to_string()))?;
+        Ok(Self { scatter })
+    }
+
+    /// Queues a write of `bytes` at `addr`.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - Virtual address to write to.
+    /// * `bytes` - Bytes to write.
+    pub fn prepare(&self, addr: u64, bytes: &[u8]) -> Result<(), DmaError> {
+        self.scatter.prepare_write(addr, bytes).map_err(|e| DmaError::Scatter(e.to_string()))
+    }
+
+    /// Queues a write of a value of type `T` at `addr`.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - Virtual address to write to.
+    /// * `value` - Reference to the value to write.
+    pub fn prepare_value<T: Pod>(&self, addr: u64, value: &T) -> Result<(), DmaError> {
+        self.scatter.prepare_write_as(addr, value).map_err(|e| DmaError::Scatter(e.to_string()))
+    }
+
+    /// Performs all queued writes in one operation.
+    pub fn execute(&self) -> Result<(), DmaError> {
+        self.scatter.execute().map_err(|e| DmaError::Scatter(e.to_string()))
+    }
+
+    /// Removes all queued writes so the writer can be reused.
+    pub fn clear(&self) -> Result<(), DmaError> {
+        self.scatter.clear().map_err(|e| DmaError::Scatter(e.to_string()))
+    }
+}
+
+/// A field of a [`StructReader`].
+#[derive(Debug, Clone)]
+struct Field {
+    name: String,
+    offset: u64,
+    len: usize,
+}
+
+/// Reads sparse fields at arbitrary offsets from a base address in one scatter batch.
+///
+/// Fields are declared with their type and offset, and [`read`](StructReader::read)
+/// gathers all of them with a single DMA operation. The reader can be kept and read
+/// again, e.g. once per frame for each entity.
+///
+/// # Examples
+///
+/// ```ignore
+/// let reader = StructReader::new(player)
+///     .field::<[f32; 3]>("position", 0x138)
+///     .field::<i32>("health", 0x100);
+/// let values = reader.read(&process)?;
+/// let health: i32 = values.get("health").unwrap_or(0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StructReader {
+    base: u64,
+    fields: Vec<Field>,
+}
+
+impl StructReader {
+    /// Creates a reader for the struct at `base` with no fields.
+    pub fn new(base: u64) -> Self {
+        Self { base, fields: Vec::new() }
+    }
+
+    /// Declares a field of type `T` at `offset` from the base address.
+    ///
+    /// Declaring a name twice replaces the earlier field.
+    pub fn field<T: Copy>(mut self, name: &str, offset: u64) -> Self {
+        self.fields.retain(|field| field.name != name);
+        self.fields.push(Field {
+            name: name.to_string(),
+            offset,
+            len: mem::size_of::<T>(),
+        });
+        self
+    }
+
+    /// Returns the base address of the struct.
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    /// Reads all declared fields in one scatter batch.
+    ///
+    /// Fields that cannot be read are missing from the result instead of failing the
+    /// whole read, as scattered fields often span pages that are not all present.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - Reference to a `VmmProcess` instance representing the target process.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `StructValues`, or `DmaError::Scatter` if the batch
+    /// could not be executed.
+    pub fn read(&self, process: &VmmProcess) -> Result<StructValues, DmaError> {
+        let mut values = StructValues { fields: HashMap::new() };
+        if self.fields.is_empty() {
+            return Ok(values);
+        }
+
+        let scatter = ScatterReader::new(process)?;
+        for field in &self.fields {
+            scatter.prepare(self.base.wrapping_add(field.offset), field.len)?;
+        }
+        scatter.execute()?;
+
+        for field in &self.fields {
+            if let Ok(bytes) = scatter.read_bytes(self.base.wrapping_add(field.offset), field.len) {
+                values.fields.insert(field.name.clone(), bytes);
+            }
+        }
+        Ok(values)
+    }
+}
+
+/// Field values read by a [`StructReader`].
+#[derive(Debug, Clone)]
+pub struct StructValues {
+    fields: HashMap<String, Vec<u8>>,
+}
+
+impl StructValues {
+    /// Returns the value of a field as a `T`.
+    ///
+    /// # Returns
+    ///
+    /// An `Option<T>` containing the value, or `None` if the field was not declared,
+    /// could not be read, or was declared with a type of a different size.
+    pub fn get<T: Pod>(&self, name: &str) -> Option<T> {
+        self.fields
+            .get(name)
+            .filter(|bytes| bytes.len() == mem::size_of::<T>())
+            .map(|bytes| from_bytes(bytes))
+    }
+
+    /// Returns the raw bytes of a field, or `None` if it was not declared or could not
+    /// be read.
+    pub fn bytes(&self, name: &str) -> Option<&[u8]> {
+        self.fields.get(name).map(Vec::as_slice)
+    }
+
+    /// Whether the field was read successfully.
+    pub fn contains(&self, name: &str) -> bool {
+        self.fields.contains_key(name)
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{fix_cr3, read_process, write, DmaError, Pod};
+use memprocfs::{Vmm, VmmProcess};
+
+/// A `Vmm` attached to a single process, as returned by [`attach`].
+///
+/// # Examples
+///
+/// ```ignore
+/// let session = attach("vmm.dll", &["", "-device", "fpga"], "game.exe")?;
+/// let client = session.module_base("client.dll")?;
+/// let health: i32 = session.read(client + 0x100)?;
+/// ```
+pub struct Session {
+    vmm: Vmm<'static>,
+    pid: u32,
+}
+
+impl Session {
+    /// Returns the underlying `Vmm`.
+    pub fn vmm(&self) -> &Vmm<'static> {
+        &self.vmm
+    }
+
+    /// Returns the PID of the attached process.
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Returns a handle to the attached process.
+    pub fn process(&self) -> VmmProcess<'_> {
+        VmmProcess { vmm: &self.vmm, pid: self.pid }
+    }
+
+    /// Reads a value of type `T` from the attached process.
+    pub fn read<T: Pod>(&self, addr: u64) -> Result<T, DmaError> {
+        read_process(&self.process(), addr)
+    }
+
+    /// Writes a value of type `T` to the attached process.
+    pub fn write<T: Pod>(&self, addr: u64, value: &T) -> Result<(), DmaError> {
+        write(&self.process(), addr, value)
+    }
+
+    /// Returns the base address of a module loaded in the attached process.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the base address, or `DmaError::ModuleNotFound` if the
+    /// module is not loaded.
+    pub fn module_base(&self, module_name: &str) -> Result<u64, DmaError> {
+        self.process()
+            .get_module_base(module_name)
+            .map_err(|_| DmaError::ModuleNotFound(module_name.to_string()))
+    }
+
+    /// Closes the session and releases the `Vmm` and its device handle.
+    ///
+    /// Dropping the session has the same effect; see [`close`](crate::close).
+    pub fn close(self) {
+        crate::close(self.vmm);
+    }
+}
+
+/// Initializes a `Vmm`, finds a process by name and fixes its CR3 if needed.
+///
+/// The process' main module is used to check the DTB; [`fix_cr3`] only runs when that
+/// module cannot be resolved with the DTB memprocfs found itself, and it waits for
+/// procinfo to complete.
+///
+/// # Arguments
+///
+/// * `vmm_path` - Path to the VMM (Virtual Machine Monitor).
+/// * `args` - Arguments to pass to the VMM.
+/// * `process_name` - Name of the process to attach to, e.g. `game.exe`.
+///
+/// # Returns
+///
+/// A `Result` containing the `Session`, `DmaError::VmmInit` if initialization failed,
+/// `DmaError::ProcessNotFound` if the process is not running, or
+/// `DmaError::Cr3Resolution` if no working DTB could be found.
+///
+/// # Examples
+///
+/// ```ignore
+/// let session = attach("vmm.dll", &["", "-device", "fpga"], "game.exe")?;
+/// println!("Attached to PID {}", session.pid());
+/// ```
+pub fn attach(vmm_path: &str, args: &[&str], process_name: &str) -> Result<Session, DmaError> {
+    let args = args.to_vec();
+    let vmm = Vmm::new(vmm_path, &args).map_err(|e| DmaError::VmmInit(e.to_string()))?;
+    let pid = vmm
+        .process_from_name(process_name)
+        .map_err(|_| DmaError::ProcessNotFound(process_name.to_string()))?
+        .pid;
+    let session = Session { vmm, pid };
+
+    let process = session.process();
+    if process.get_module_base(process_name).is_err() {
+        log::debug!("{} does not resolve for PID {}, fixing CR3", process_name, pid);
+        if !fix_cr3(&session.vmm, &process, process_name, pid)? {
+            return Err(DmaError::Cr3Resolution);
+        }
+    }
+
+    Ok(session)
+}
@@ -0,0 +1,136 @@
+use crate::{read, read_bytes, write_pid, DmaError, Pod};
+use memprocfs::Vmm;
+use std::sync::{mpsc, Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::{thread, time};
+
+/// A `Vmm` that can be shared between threads, typically behind an `Arc`.
+///
+/// The native memprocfs API is thread-safe, so reads take a shared lock and run
+/// concurrently. Writes and anything that reconfigures the `Vmm` (such as
+/// `set_config` or [`fix_cr3`](crate::fix_cr3)) take an exclusive lock, so they never
+/// interleave with in-flight reads.
+///
+/// # Examples
+///
+/// ```ignore
+/// let shared = Arc::new(SharedVmm::new(init(vmm_path, &args)?));
+/// let entities = Arc::clone(&shared);
+/// thread::spawn(move || {
+///     let position: [f32; 3] = entities.read(pid, entity + 0x10).unwrap();
+/// });
+/// let health: i32 = shared.read(pid, local_player + 0x100)?;
+/// ```
+pub struct SharedVmm {
+    vmm: RwLock<Vmm<'static>>,
+}
+
+impl SharedVmm {
+    /// Wraps a `Vmm` for shared use.
+    pub fn new(vmm: Vmm<'static>) -> Self {
+        Self { vmm: RwLock::new(vmm) }
+    }
+
+    /// Reads a value of type `T` from a process under the shared lock.
+    pub fn read<T: Pod>(&self, pid: u32, addr: u64) -> Result<T, DmaError> {
+        read(&self.lock_shared(), pid, addr)
+    }
+
+    /// Reads exactly `len` bytes from a process under the shared lock.
+    pub fn read_bytes(&self, pid: u32, addr: u64, len: usize) -> Result<Vec<u8>, DmaError> {
+        let vmm = self.lock_shared();
+        let process = vmm
+            .process_from_pid(pid)
+            .map_err(|_| DmaError::ProcessNotFound(format!("with PID {}", pid)))?;
+        read_bytes(&process, addr, len)
+    }
+
+    /// Writes a value of type `T` to a process under the exclusive lock.
+    pub fn write<T: Pod>(&self, pid: u32, addr: u64, value: &T) -> Result<(), DmaError> {
+        write_pid(&self.lock_exclusive(), pid, addr, value)
+    }
+
+    /// Runs `f` with shared access to the `Vmm`, concurrently with other readers.
+    pub fn with<R>(&self, f: impl FnOnce(&Vmm<'static>) -> R) -> R {
+        f(&self.lock_shared())
+    }
+
+    /// Runs `f` with exclusive access to the `Vmm`, e.g. to change its configuration.
+    pub fn with_exclusive<R>(&self, f: impl FnOnce(&Vmm<'static>) -> R) -> R {
+        f(&self.lock_exclusive())
+    }
+
+    /// Runs `f` on a worker thread with shared access, giving up after `timeout`.
+    ///
+    /// memprocfs has no read timeout of its own, so a read that hangs on a struggling
+    /// FPGA link would otherwise block the caller indefinitely. When the deadline passes
+    /// the worker is left to finish in the background and its result is discarded.
+    /// Until it does, it keeps holding the shared lock, so exclusive operations such as
+    /// [`write`](SharedVmm::write) wait for the hung read to return.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum time to wait for `f`.
+    /// * `f` - Operation to run.
+    ///
+    /// # Returns
+    ///
+    /// The result of `f`, or `DmaError::Timeout` if it did not complete in time.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let shared = Arc::new(SharedVmm::new(vmm));
+    /// let positions = shared.with_timeout(Duration::from_millis(5), move |vmm| {
+    ///     let process = vmm.process_from_pid(pid).map_err(|e| DmaError::ProcessNotFound(e.to_string()))?;
+    ///     read_array::<[f32; 3]>(&process, positions_addr, 64)
+    /// })?;
+    /// let health: i32 = shared.read_timeout(pid, local_player + 0x100, Duration::from_millis(5))?;
+    /// ```
+    pub fn with_timeout<R, F>(self: &Arc<Self>, timeout: time::Duration, f: F) -> Result<R, DmaError>
+    where
+        R: Send + 'static,
+        F: FnOnce(&Vmm<'static>) -> Result<R, DmaError> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let shared = Arc::clone(self);
+        thread::spawn(move || {
+            // The receiver is gone if the caller already timed out.
+            let _ = sender.send(shared.with(f));
+        });
+        receiver.recv_timeout(timeout).unwrap_or_else(|e| match e {
+            mpsc::RecvTimeoutError::Timeout => {
+                log::debug!("Operation did not complete within {:?}", timeout);
+                Err(DmaError::Timeout)
+            }
+            mpsc::RecvTimeoutError::Disconnected => Err(DmaError::Disconnected),
+        })
+    }
+
+    /// Reads a value of type `T` from a process, giving up after `timeout`.
+    ///
+    /// See [`with_timeout`](SharedVmm::with_timeout) for how the deadline is enforced.
+    pub fn read_timeout<T: Pod + Send + 'static>(self: &Arc<Self>, pid: u32, addr: u64, timeout: time::Duration) -> Result<T, DmaError> {
+        self.with_timeout(timeout, move |vmm| read(vmm, pid, addr))
+    }
+
+    /// Reads exactly `len` bytes from a process, giving up after `timeout`.
+    ///
+    /// See [`with_timeout`](SharedVmm::with_timeout) for how the deadline is enforced.
+    pub fn read_bytes_timeout(self: &Arc<Self>, pid: u32, addr: u64, len: usize, timeout: time::Duration) -> Result<Vec<u8>, DmaError> {
+        self.with_timeout(timeout, move |vmm| {
+            let process = vmm
+                .process_from_pid(pid)
+                .map_err(|_| DmaError::ProcessNotFound(format!("with PID {}", pid)))?;
+            read_bytes(&process, addr, len)
+        })
+    }
+
+    // A panic in another thread leaves the `Vmm` itself intact, so poisoning is ignored.
+    fn lock_shared(&self) -> RwLockReadGuard<'_, Vmm<'static>> {
+        self.vmm.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn lock_exclusive(&self) -> RwLockWriteGuard<'_, Vmm<'static>> {
+        self.vmm.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
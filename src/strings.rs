@@ -0,0 +1,455 @@
+use crate::{read_bytes, read_process, DmaError, Pod, PointerWidth};
+use memprocfs::VmmProcess;
+
+/// Number of bytes read from the target per string read step.
+const STRING_CHUNK_SIZE: usize = 0x100;
+
+/// Size of a page, which string and pointer table reads never cross in one step.
+const PAGE_SIZE: u64 = 0x1000;
+
+/// Reads a null-terminated string from the memory of a process.
+///
+/// The string is read in chunks of up to 256 bytes that do not cross page boundaries,
+/// until a null byte is found or `max_len` bytes have been read, and is decoded as
+/// lossy UTF-8. A chunk that cannot be read completely fails the read rather than
+/// being taken as the end of the string.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address of the string.
+/// * `max_len` - Maximum number of bytes to read.
+///
+/// # Returns
+///
+/// A `Result` containing the string without its terminator, or `DmaError::Read` if the
+/// memory could not be read. An empty string is returned if the first byte is null.
+///
+/// # Examples
+///
+/// ```ignore
+/// let name = read_string(&process, player + 0x40, 32)?;
+/// println!("Player: {}", name);
+/// ```
+pub fn read_string(process: &VmmProcess, addr: u64, max_len: usize) -> Result<String, DmaError> {
+    read_string_with(|addr, len| read_bytes(process, addr, len), addr, max_len)
+}
+
+/// Implements [`read_string`] on top of `read`, which must return exactly `len` bytes.
+fn read_string_with(mut read: impl FnMut(u64, usize) -> Result<Vec<u8>, DmaError>, addr: u64, max_len: usize) -> Result<String, DmaError> {
+    let mut bytes = Vec::new();
+
+    while bytes.len() < max_len {
+        let chunk_addr = addr + bytes.len() as u64;
+        let chunk = read(chunk_addr, chunk_len(chunk_addr, max_len - bytes.len()))?;
+        if let Some(end) = chunk.iter().position(|&b| b == 0) {
+            bytes.extend_from_slice(&chunk[..end]);
+            break;
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Reads a table of fixed-size, inline C-string buffers, e.g. `char names[64][32]`.
+///
+/// The whole table is read in one read and every string is cut at its first null
+/// byte, or after `max_len` bytes if it has none, and decoded as lossy UTF-8.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address of the first buffer.
+/// * `count` - Number of buffers in the table.
+/// * `stride` - Distance in bytes between the starts of consecutive buffers.
+/// * `max_len` - Maximum length of each string, usually the buffer size.
+///
+/// # Returns
+///
+/// A `Result` containing one `String` per buffer, or `DmaError::Read` if the table
+/// could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let names = read_cstr_array(&process, name_table, 64, 32, 32)?;
+/// ```
+pub fn read_cstr_array(process: &VmmProcess, addr: u64, count: usize, stride: usize, max_len: usize) -> Result<Vec<String>, DmaError> {
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+    let len = (count - 1)
+        .checked_mul(stride)
+        .and_then(|len| len.checked_add(max_len))
+        .ok_or(DmaError::Read { addr, len: usize::MAX })?;
+    let table = read_bytes(process, addr, len)?;
+    Ok(split_cstr_table(&table, count, stride, max_len))
+}
+
+/// Extracts `count` null-terminated strings from a table read by [`read_cstr_array`].
+fn split_cstr_table(table: &[u8], count: usize, stride: usize, max_len: usize) -> Vec<String> {
+    (0..count)
+        .map(|index| {
+            let start = index * stride;
+            let buffer = &table[start..start + max_len];
+            let end = buffer.iter().position(|&b| b == 0).unwrap_or(max_len);
+            String::from_utf8_lossy(&buffer[..end]).into_owned()
+        })
+        .collect()
+}
+
+/// Reads a null-terminated array of C-string pointers, e.g. `argv` or `envp`.
+///
+/// Pointers are read from `addr` until a null pointer or `max_count` pointers, and each
+/// is then read with [`read_string`]. The pointer table is read a page at a time, so a
+/// terminator at the end of the last mapped page is still found.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address of the first pointer.
+/// * `width` - Size of the pointers in target memory.
+/// * `max_count` - Maximum number of strings to read, excluding the terminator.
+/// * `max_len` - Maximum length of each string.
+///
+/// # Returns
+///
+/// A `Result` containing the strings in array order, or `DmaError::Read` if the pointer
+/// table or a string could not be read. If no terminator is found within `max_count`
+/// pointers, the first `max_count` strings are returned.
+///
+/// # Examples
+///
+/// ```ignore
+/// let argv = read_null_terminated_ptr_array(&process, argv_addr, PointerWidth::U64, 256, 0x1000)?;
+/// ```
+pub fn read_null_terminated_ptr_array(process: &VmmProcess, addr: u64, width: PointerWidth, max_count: usize, max_len: usize) -> Result<Vec<String>, DmaError> {
+    let mut pointers = Vec::new();
+    let mut table_addr = addr;
+
+    while pointers.len() < max_count {
+        let page_end = (table_addr | (PAGE_SIZE - 1)) + 1;
+        let count = ((page_end - table_addr) as usize / width.size())
+            .max(1)
+            .min(max_count - pointers.len());
+        let len = count * width.size();
+        let table = read_bytes(process, table_addr, len)?;
+        let terminated = collect_until_null(&table, width, &mut pointers);
+        if terminated {
+            break;
+        }
+        table_addr += len as u64;
+    }
+
+    pointers.into_iter().map(|pointer| read_string(process, pointer, max_len)).collect()
+}
+
+/// Appends the pointers in `table` to `pointers` up to the first null pointer, and
+/// returns whether one was found.
+fn collect_until_null(table: &[u8], width: PointerWidth, pointers: &mut Vec<u64>) -> bool {
+    for pointer in table.chunks_exact(width.size()) {
+        let pointer = match width {
+            PointerWidth::U32 => u32::from_le_bytes(pointer.try_into().unwrap()) as u64,
+            PointerWidth::U64 => u64::from_le_bytes(pointer.try_into().unwrap()),
+        };
+        if pointer == 0 {
+            return true;
+        }
+        pointers.push(pointer);
+    }
+    false
+}
+
+/// Reads bytes from the memory of a process until a delimiter sequence is found.
+///
+/// This generalizes [`read_string`] to arbitrary terminators. The memory is read in
+/// chunks that do not cross page boundaries, and a delimiter straddling two chunks is
+/// still found. Only bytes that were actually read are returned or searched.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address to start reading at.
+/// * `delimiter` - Byte sequence that terminates the data.
+/// * `max_len` - Maximum number of bytes to read, including the delimiter.
+///
+/// # Returns
+///
+/// A `Result` containing the bytes before the delimiter, or all `max_len` bytes if the
+/// delimiter was not found. Returns `DmaError::InvalidPattern` if `delimiter` is empty
+/// or `DmaError::Read` if the memory could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let record = read_until(&process, blob, b"\r\n", 0x1000)?;
+/// ```
+pub fn read_until(process: &VmmProcess, addr: u64, delimiter: &[u8], max_len: usize) -> Result<Vec<u8>, DmaError> {
+    read_until_with(|addr, len| read_bytes(process, addr, len), addr, delimiter, max_len)
+}
+
+/// Implements [`read_until`] on top of `read`, which must return exactly `len` bytes.
+fn read_until_with(mut read: impl FnMut(u64, usize) -> Result<Vec<u8>, DmaError>, addr: u64, delimiter: &[u8], max_len: usize) -> Result<Vec<u8>, DmaError> {
+    if delimiter.is_empty() {
+        return Err(DmaError::InvalidPattern("delimiter is empty".to_string()));
+    }
+    let mut bytes = Vec::new();
+
+    while bytes.len() < max_len {
+        let chunk_addr = addr + bytes.len() as u64;
+        let chunk = read(chunk_addr, chunk_len(chunk_addr, max_len - bytes.len()))?;
+        // Search from the end of the previous chunk so a split delimiter is found.
+        let search_start = bytes.len().saturating_sub(delimiter.len() - 1);
+        bytes.extend_from_slice(&chunk);
+        if let Some(offset) = bytes[search_start..].windows(delimiter.len()).position(|window| window == delimiter) {
+            bytes.truncate(search_start + offset);
+            return Ok(bytes);
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Returns the length of the next string read step at `addr`, at most `remaining` bytes.
+///
+/// Steps end at page boundaries, so a string ending just before an unmapped page is
+/// read without touching that page, and every step is either read fully or fails.
+fn chunk_len(addr: u64, remaining: usize) -> usize {
+    let to_page_end = (PAGE_SIZE - addr % PAGE_SIZE) as usize;
+    STRING_CHUNK_SIZE.min(remaining).min(to_page_end)
+}
+
+/// Reads a null-terminated UTF-16 string from the memory of a process.
+///
+/// The string is read in chunks that do not cross page boundaries until a null code
+/// unit is found or `max_chars` code units have been read, and is decoded with
+/// `String::from_utf16_lossy`. A chunk that cannot be read completely fails the read
+/// rather than being taken as the end of the string.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address of the string.
+/// * `max_chars` - Maximum number of UTF-16 code units to read.
+///
+/// # Returns
+///
+/// A `Result` containing the decoded string without its terminator, or `DmaError::Read`
+/// if the memory could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let path = read_wstring(&process, buffer_addr, 260)?;
+/// ```
+pub fn read_wstring(process: &VmmProcess, addr: u64, max_chars: usize) -> Result<String, DmaError> {
+    read_wstring_with(|addr, len| read_bytes(process, addr, len), addr, max_chars)
+}
+
+/// Implements [`read_wstring`] on top of `read`, which must return exactly `len` bytes.
+fn read_wstring_with(mut read: impl FnMut(u64, usize) -> Result<Vec<u8>, DmaError>, addr: u64, max_chars: usize) -> Result<String, DmaError> {
+    let mut units: Vec<u16> = Vec::new();
+
+    while units.len() < max_chars {
+        let chunk_addr = addr + (units.len() * 2) as u64;
+        // Whole code units only; a unit straddling a page boundary is read on its own.
+        let len = (chunk_len(chunk_addr, (max_chars - units.len()) * 2) & !1).max(2);
+        let chunk = read(chunk_addr, len)?;
+        let chunk_units = chunk.chunks_exact(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]]));
+        let before = units.len();
+        units.extend(chunk_units.take_while(|&unit| unit != 0));
+        if units.len() - before < len / 2 {
+            break;
+        }
+    }
+
+    Ok(String::from_utf16_lossy(&units))
+}
+
+/// In-memory layout of a 64-bit Windows `UNICODE_STRING`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct UnicodeString {
+    length: u16,
+    maximum_length: u16,
+    padding: u32,
+    buffer: u64,
+}
+
+// SAFETY: integer fields only, padded explicitly.
+unsafe impl Pod for UnicodeString {}
+
+/// Largest `Length` accepted in a `UNICODE_STRING`, in bytes.
+///
+/// Windows caps `Length` at `u16::MAX` but real strings are far shorter, so larger
+/// values almost always mean the structure was read from the wrong address.
+const UNICODE_STRING_MAX_LEN: u16 = 0x8000;
+
+/// Reads a 64-bit `UNICODE_STRING` structure and decodes the string it points to.
+///
+/// The structure is validated before its buffer is read: `Length` must be even, no
+/// larger than `MaximumLength` and at most 32 KiB, and the buffer must not be null
+/// unless the string is empty. Exactly `Length` bytes are decoded, so embedded null
+/// characters are kept.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address of the `UNICODE_STRING` structure.
+///
+/// # Returns
+///
+/// A `Result` containing the decoded string, `DmaError::InvalidString` if the
+/// structure is malformed, or `DmaError::Read` if the structure or its buffer could
+/// not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let image_path = read_unicode_string(&process, process_parameters + 0x60)?;
+/// ```
+pub fn read_unicode_string(process: &VmmProcess, addr: u64) -> Result<String, DmaError> {
+    let unicode_string: UnicodeString = read_process(process, addr)?;
+    read_unicode_buffer(
+        process,
+        addr,
+        unicode_string.length,
+        unicode_string.maximum_length,
+        unicode_string.buffer,
+    )
+}
+
+/// In-memory layout of a 32-bit Windows `UNICODE_STRING`, as used by WOW64 processes.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct UnicodeString32 {
+    length: u16,
+    maximum_length: u16,
+    buffer: u32,
+}
+
+// SAFETY: integer fields only, without padding.
+unsafe impl Pod for UnicodeString32 {}
+
+/// Reads a 32-bit `UNICODE_STRING` structure and decodes the string it points to.
+pub(crate) fn read_unicode_string32(process: &VmmProcess, addr: u64) -> Result<String, DmaError> {
+    let unicode_string: UnicodeString32 = read_process(process, addr)?;
+    read_unicode_buffer(
+        process,
+        addr,
+        unicode_string.length,
+        unicode_string.maximum_length,
+        unicode_string.buffer as u64,
+    )
+}
+
+/// Validates the fields of a `UNICODE_STRING` read at `addr` and decodes its buffer.
+fn read_unicode_buffer(process: &VmmProcess, addr: u64, length: u16, maximum_length: u16, buffer: u64) -> Result<String, DmaError> {
+    if length == 0 {
+        return Ok(String::new());
+    }
+    let invalid = |reason: &str| Err(DmaError::InvalidString(format!("UNICODE_STRING at 0x{:X} {}", addr, reason)));
+    if !length.is_multiple_of(2) {
+        return invalid("has an odd length");
+    }
+    if length > maximum_length || length > UNICODE_STRING_MAX_LEN {
+        return invalid("has an implausible length");
+    }
+    if buffer == 0 {
+        return invalid("has a null buffer");
+    }
+
+    let bytes = read_bytes(process, buffer, length as usize)?;
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+        .collect();
+    Ok(String::from_utf16_lossy(&units))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MemoryReader, MockMemory};
+
+    #[test]
+    fn split_cstr_table_cuts_at_the_first_null() {
+        let table = b"ak47\0\0\0\0m4a1\0xyz";
+        assert_eq!(split_cstr_table(table, 2, 8, 8), vec!["ak47", "m4a1"]);
+    }
+
+    #[test]
+    fn split_cstr_table_limits_unterminated_strings() {
+        let table = b"abcdefgh\0\0\0\0\0\0\0\0ij";
+        assert_eq!(split_cstr_table(table, 2, 16, 2), vec!["ab", "ij"]);
+    }
+
+    #[test]
+    fn split_cstr_table_handles_empty_buffers() {
+        assert_eq!(split_cstr_table(&[0; 12], 3, 4, 4), vec!["", "", ""]);
+    }
+
+    #[test]
+    fn collect_until_null_stops_at_the_terminator() {
+        let table = [0x10, 0, 0, 0, 0x20, 0, 0, 0, 0, 0, 0, 0, 0x30, 0, 0, 0];
+        let mut pointers = Vec::new();
+        assert!(collect_until_null(&table, PointerWidth::U32, &mut pointers));
+        assert_eq!(pointers, vec![0x10, 0x20]);
+    }
+
+    #[test]
+    fn collect_until_null_continues_without_a_terminator() {
+        let table = 0x7FF6_0000_1000u64.to_le_bytes();
+        let mut pointers = vec![0x7FF6_0000_0800];
+        assert!(!collect_until_null(&table, PointerWidth::U64, &mut pointers));
+        assert_eq!(pointers, vec![0x7FF6_0000_0800, 0x7FF6_0000_1000]);
+    }
+
+    #[test]
+    fn read_until_finds_a_delimiter_split_across_chunks() {
+        let mut bytes = vec![b'a'; 0x200];
+        bytes[STRING_CHUNK_SIZE - 1..STRING_CHUNK_SIZE + 1].copy_from_slice(b"\r\n");
+        let mock = MockMemory::new().with_memory(1, 0x1000, bytes);
+        let read = |addr, len| mock.read_memory(1, addr, len);
+        let record = read_until_with(read, 0x1000, b"\r\n", 0x200).unwrap();
+        assert_eq!(record.len(), STRING_CHUNK_SIZE - 1);
+    }
+
+    #[test]
+    fn read_until_fails_instead_of_reading_past_mapped_memory() {
+        let mock = MockMemory::new().with_memory(1, 0x1F00, vec![b'a'; 0x100]);
+        let read = |addr, len| mock.read_memory(1, addr, len);
+        assert!(matches!(read_until_with(read, 0x1F00, b"\0\0", 0x200), Err(DmaError::Read { addr: 0x2000, .. })));
+    }
+
+    #[test]
+    fn read_string_stops_at_a_terminator_before_an_unmapped_page() {
+        let mock = MockMemory::new().with_memory(1, 0x1FF0, b"player\0garbage\0\0".to_vec());
+        let read = |addr, len| mock.read_memory(1, addr, len);
+        assert_eq!(read_string_with(read, 0x1FF0, 0x100).unwrap(), "player");
+    }
+
+    #[test]
+    fn read_string_fails_on_an_unreadable_continuation() {
+        let mock = MockMemory::new().with_memory(1, 0x1FF0, vec![b'a'; 0x10]);
+        let read = |addr, len| mock.read_memory(1, addr, len);
+        assert!(matches!(read_string_with(read, 0x1FF0, 0x100), Err(DmaError::Read { addr: 0x2000, .. })));
+    }
+
+    #[test]
+    fn read_wstring_reads_units_straddling_a_page_boundary() {
+        let mut bytes: Vec<u8> = "C:\\x\0".encode_utf16().flat_map(u16::to_le_bytes).collect();
+        bytes.resize(0x200, 0xEE);
+        let mock = MockMemory::new().with_memory(1, 0x1FFB, bytes);
+        let read = |addr, len| mock.read_memory(1, addr, len);
+        assert_eq!(read_wstring_with(read, 0x1FFB, 260).unwrap(), "C:\\x");
+    }
+
+    #[test]
+    fn read_wstring_fails_on_an_unreadable_continuation() {
+        let mock = MockMemory::new().with_memory(1, 0x1FF0, vec![b'a'; 0x10]);
+        let read = |addr, len| mock.read_memory(1, addr, len);
+        assert!(matches!(read_wstring_with(read, 0x1FF0, 260), Err(DmaError::Read { addr: 0x2000, .. })));
+    }
+}
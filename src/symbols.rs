@@ -0,0 +1,79 @@
+use crate::kernel::system_process;
+use crate::DmaError;
+use memprocfs::{Vmm, VmmPdb};
+
+/// Resolves the address of a symbol from the debug symbols (PDB) of a kernel module.
+///
+/// memprocfs downloads and caches the PDB of the module on first use. `nt` is accepted
+/// as the module name of the kernel itself; other names are looked up among the
+/// kernel modules, e.g. `win32kbase.sys`. Resolving symbols at runtime avoids
+/// hardcoding addresses that change with every Windows build.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `module` - Name of the module, e.g. `nt` or `win32kbase.sys`.
+/// * `symbol` - Name of the symbol, e.g. `PsActiveProcessHead`.
+///
+/// # Returns
+///
+/// A `Result` containing the symbol's address, or `DmaError::Symbol` if the PDB is not
+/// available, e.g. because the `Vmm` was started with `-disable-symbols` or the symbol
+/// server could not be reached, or if the PDB has no such symbol.
+///
+/// # Examples
+///
+/// ```ignore
+/// let head = resolve_symbol(&vmm, "nt", "PsActiveProcessHead")?;
+/// let first: u64 = read_kernel(&vmm, head)?;
+/// ```
+pub fn resolve_symbol(vmm: &Vmm, module: &str, symbol: &str) -> Result<u64, DmaError> {
+    with_pdb(vmm, module, |pdb| {
+        pdb.symbol_address_from_name(symbol)
+            .map_err(|_| DmaError::Symbol(format!("{}!{}: not in the PDB, or the PDB could not be loaded", module, symbol)))
+    })
+}
+
+/// Runs `f` with the PDB of a kernel module, `nt` being the kernel itself.
+pub(crate) fn with_pdb<R>(vmm: &Vmm, module: &str, f: impl FnOnce(&VmmPdb) -> Result<R, DmaError>) -> Result<R, DmaError> {
+    if module.eq_ignore_ascii_case("nt") {
+        let kernel = vmm.kernel();
+        return f(&kernel.pdb());
+    }
+    let system = system_process(vmm);
+    let pdb = system.pdb_from_module_name(module).map_err(|_| {
+        DmaError::Symbol(format!("{}: PDB not available; symbols may be disabled or the symbol server unreachable", module))
+    })?;
+    f(&pdb)
+}
+
+/// Resolves the offset of a field within a structure from a kernel module's debug symbols.
+///
+/// Kernel structure layouts change between Windows builds, so resolving offsets such
+/// as `_EPROCESS.UniqueProcessId` at runtime keeps tools working across updates.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `module` - Name of the module defining the type, e.g. `nt`.
+/// * `struct_name` - Name of the type, e.g. `_EPROCESS`.
+/// * `field_name` - Name of the field, e.g. `UniqueProcessId`.
+///
+/// # Returns
+///
+/// A `Result` containing the field's offset in bytes, or `DmaError::Symbol` if the PDB
+/// is not available or has no such type or field.
+///
+/// # Examples
+///
+/// ```ignore
+/// let pid_offset = struct_offset(&vmm, "nt", "_EPROCESS", "UniqueProcessId")?;
+/// let pid: u64 = read_kernel(&vmm, eprocess + pid_offset as u64)?;
+/// ```
+pub fn struct_offset(vmm: &Vmm, module: &str, struct_name: &str, field_name: &str) -> Result<u32, DmaError> {
+    with_pdb(vmm, module, |pdb| {
+        pdb.type_child_offset(struct_name, field_name).map_err(|_| {
+            DmaError::Symbol(format!("{}!{}.{}: not in the PDB, or the PDB could not be loaded", module, struct_name, field_name))
+        })
+    })
+}
@@ -0,0 +1,174 @@
+use crate::{read_process, DmaError};
+use memprocfs::VmmProcess;
+use std::mem;
+
+/// Reads three consecutive `f32`s, e.g. a world position.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address of the first component.
+///
+/// # Returns
+///
+/// A `Result` containing the vector, or `DmaError::Read` if it could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let [x, y, z] = read_vec3(&process, player + 0x138)?;
+/// ```
+pub fn read_vec3(process: &VmmProcess, addr: u64) -> Result<[f32; 3], DmaError> {
+    read_process(process, addr)
+}
+
+/// Reads four consecutive `f32`s, e.g. a quaternion or a homogeneous position.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address of the first component.
+///
+/// # Returns
+///
+/// A `Result` containing the vector, or `DmaError::Read` if it could not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let rotation = read_vec4(&process, bone + 0x10)?;
+/// ```
+pub fn read_vec4(process: &VmmProcess, addr: u64) -> Result<[f32; 4], DmaError> {
+    read_process(process, addr)
+}
+
+/// Reads three consecutive `f32`s and rejects the result if every component is NaN.
+///
+/// An all-NaN vector almost always means the address was wrong or the object was
+/// freed, so it is reported as a failed read instead of being drawn off-screen.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address of the first component.
+///
+/// # Returns
+///
+/// A `Result` containing the vector, or `DmaError::Read` if it could not be read or
+/// only contains NaNs.
+///
+/// # Examples
+///
+/// ```ignore
+/// let Ok(position) = read_vec3_checked(&process, entity + 0x138) else {
+///     continue;
+/// };
+/// ```
+pub fn read_vec3_checked(process: &VmmProcess, addr: u64) -> Result<[f32; 3], DmaError> {
+    reject_nan(read_vec3(process, addr)?, addr)
+}
+
+/// Reads four consecutive `f32`s and rejects the result if every component is NaN.
+///
+/// See [`read_vec3_checked`].
+pub fn read_vec4_checked(process: &VmmProcess, addr: u64) -> Result<[f32; 4], DmaError> {
+    reject_nan(read_vec4(process, addr)?, addr)
+}
+
+fn reject_nan<const N: usize>(vector: [f32; N], addr: u64) -> Result<[f32; N], DmaError> {
+    if vector.iter().all(|component| component.is_nan()) {
+        return Err(DmaError::Read { addr, len: mem::size_of::<[f32; N]>() });
+    }
+    Ok(vector)
+}
+
+/// Reads a row-major 4x4 matrix of `f32`s, e.g. a view-projection matrix.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address of the first element.
+///
+/// # Returns
+///
+/// A `Result` containing the matrix as rows, or `DmaError::Read` if its 64 bytes could
+/// not be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let view_matrix = read_matrix4x4(&process, client + 0x1820)?;
+/// ```
+pub fn read_matrix4x4(process: &VmmProcess, addr: u64) -> Result<[[f32; 4]; 4], DmaError> {
+    read_process(process, addr)
+}
+
+/// Smallest clip-space `w` of a point that is considered in front of the camera.
+const MIN_CLIP_W: f32 = 0.001;
+
+/// Projects a world position onto the screen with a row-major view-projection matrix.
+///
+/// The position is transformed as a column vector, `clip = matrix * [x, y, z, 1]`, as
+/// with the matrices of Direct3D games, and mapped from normalized device coordinates
+/// to pixels with the origin at the top left. Use the transposed matrix for games that
+/// store it column-major.
+///
+/// # Arguments
+///
+/// * `matrix` - View-projection matrix, e.g. from [`read_matrix4x4`].
+/// * `pos` - World position to project.
+/// * `screen_w` - Screen width in pixels.
+/// * `screen_h` - Screen height in pixels.
+///
+/// # Returns
+///
+/// An `Option` containing the screen position, or `None` if the point is behind the
+/// camera. Points in front of the camera but off-screen are still returned.
+///
+/// # Examples
+///
+/// ```ignore
+/// let view_matrix = read_matrix4x4(&process, client + 0x1820)?;
+/// if let Some((x, y)) = world_to_screen(&view_matrix, position, 1920.0, 1080.0) {
+///     draw_box(x, y);
+/// }
+/// ```
+pub fn world_to_screen(matrix: &[[f32; 4]; 4], pos: [f32; 3], screen_w: f32, screen_h: f32) -> Option<(f32, f32)> {
+    let row = |row: &[f32; 4]| row[0] * pos[0] + row[1] * pos[1] + row[2] * pos[2] + row[3];
+    let w = row(&matrix[3]);
+    if w < MIN_CLIP_W {
+        return None;
+    }
+    let ndc_x = row(&matrix[0]) / w;
+    let ndc_y = row(&matrix[1]) / w;
+    Some((screen_w / 2.0 * (1.0 + ndc_x), screen_h / 2.0 * (1.0 - ndc_y)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Perspective matrix with the camera at the origin looking down +z.
+    const PERSPECTIVE: [[f32; 4]; 4] = [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+    ];
+
+    #[test]
+    fn world_to_screen_centers_points_on_the_view_axis() {
+        assert_eq!(world_to_screen(&PERSPECTIVE, [0.0, 0.0, 10.0], 1920.0, 1080.0), Some((960.0, 540.0)));
+    }
+
+    #[test]
+    fn world_to_screen_maps_up_and_right_to_the_top_right() {
+        assert_eq!(world_to_screen(&PERSPECTIVE, [5.0, 5.0, 10.0], 100.0, 100.0), Some((75.0, 25.0)));
+    }
+
+    #[test]
+    fn world_to_screen_rejects_points_behind_the_camera() {
+        assert_eq!(world_to_screen(&PERSPECTIVE, [0.0, 0.0, -10.0], 1920.0, 1080.0), None);
+        assert_eq!(world_to_screen(&PERSPECTIVE, [1.0, 1.0, 0.0], 1920.0, 1080.0), None);
+    }
+}
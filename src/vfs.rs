@@ -0,0 +1,79 @@
+use crate::DmaError;
+use memprocfs::Vmm;
+
+/// Progress of the procinfo scan, `100` once complete.
+pub const VFS_PROCINFO_PROGRESS: &str = "\\misc\\procinfo\\progress_percent.txt";
+/// Candidate DTBs found by the procinfo scan; see [`parse_dtb_lines`](crate::parse_dtb_lines).
+pub const VFS_PROCINFO_DTB: &str = "\\misc\\procinfo\\dtb.txt";
+/// Physical memory map in the format accepted by `-memmap`.
+pub const VFS_MEMORY_MAP: &str = "\\sys\\memory\\physmemmap.txt";
+
+/// Number of bytes requested per VFS read.
+const VFS_CHUNK_SIZE: u32 = 0x100000;
+
+/// Reads a whole file from the memprocfs virtual file system.
+///
+/// The file is read in chunks until memprocfs returns less than was requested, so
+/// files of any size are read completely.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `path` - Path of the file, e.g. [`VFS_PROCINFO_DTB`] or `\sys\version.txt`.
+///
+/// # Returns
+///
+/// A `Result` containing the contents of the file, or `DmaError::Vfs` if it could not
+/// be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let build = vfs_read_file(&vmm, "\\sys\\version-build.txt")?;
+/// println!("{}", String::from_utf8_lossy(&build));
+/// ```
+pub fn vfs_read_file(vmm: &Vmm, path: &str) -> Result<Vec<u8>, DmaError> {
+    let mut contents = Vec::new();
+    loop {
+        let chunk = vmm
+            .vfs_read(path, VFS_CHUNK_SIZE, contents.len() as u64)
+            .map_err(|e| DmaError::Vfs(format!("{}: {}", path, e)))?;
+        let done = chunk.len() < VFS_CHUNK_SIZE as usize;
+        contents.extend_from_slice(&chunk);
+        if done {
+            return Ok(contents);
+        }
+    }
+}
+
+/// Writes to a file in the memprocfs virtual file system, e.g. a configuration file.
+///
+/// memprocfs does not report whether a VFS write succeeded, so read the file back if
+/// the outcome matters.
+///
+/// # Arguments
+///
+/// * `vmm` - Reference to a `Vmm` instance.
+/// * `path` - Path of the file.
+/// * `data` - Bytes to write at the start of the file.
+///
+/// # Returns
+///
+/// A `Result` which is `Ok(())` once the write was issued, or `DmaError::InvalidArgument`
+/// if the path contains a null character or `data` is larger than 4 GiB.
+///
+/// # Examples
+///
+/// ```ignore
+/// vfs_write_file(&vmm, "\\conf\\config_process_show_terminated.txt", b"1")?;
+/// ```
+pub fn vfs_write_file(vmm: &Vmm, path: &str, data: &[u8]) -> Result<(), DmaError> {
+    if path.contains('\0') {
+        return Err(DmaError::InvalidArgument(format!("VFS path {:?} contains a null character", path)));
+    }
+    if u32::try_from(data.len()).is_err() {
+        return Err(DmaError::InvalidArgument(format!("{} bytes is too large for a VFS write", data.len())));
+    }
+    vmm.vfs_write(path, data.to_vec(), 0);
+    Ok(())
+}
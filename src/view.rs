@@ -0,0 +1,135 @@
+use crate::memory::from_bytes;
+use crate::{read_bytes_ex, DmaError, Pod, ReadFlags};
+use memprocfs::VmmProcess;
+use std::mem;
+
+/// A typed, bounds-checked view over bytes read from a process in one operation.
+///
+/// Read a whole structure once, then extract as many fields as needed locally without
+/// further DMA round trips or per-field allocations. Offsets are relative to the
+/// address the bytes were read from, and out-of-bounds accesses are reported as
+/// `DmaError::Read` at the address they would have read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let view = MemView::new(entity, read_bytes(&process, entity, 0x1000)?);
+/// // or: let view = snapshot(&process, entity, 0x1000)?;
+/// let health: i32 = view.get(0x100)?;
+/// let position: [f32; 3] = view.get(0x138)?;
+/// let name = view.slice(0x40, 32)?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemView {
+    base: u64,
+    bytes: Vec<u8>,
+}
+
+impl MemView {
+    /// Wraps `bytes` that were read from `base`.
+    pub fn new(base: u64, bytes: Vec<u8>) -> Self {
+        Self { base, bytes }
+    }
+
+    /// Returns the address the bytes were read from.
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    /// Returns the number of bytes in the view.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Whether the view contains no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns all bytes of the view.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the bytes of the view, consuming it.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Returns a value of type `T` at `offset`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the value, or `DmaError::Read` if it does not lie entirely
+    /// within the view.
+    pub fn get<T: Pod>(&self, offset: usize) -> Result<T, DmaError> {
+        self.slice(offset, mem::size_of::<T>()).map(from_bytes)
+    }
+
+    /// Returns `len` bytes at `offset`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the bytes, or `DmaError::Read` if they do not lie entirely
+    /// within the view.
+    pub fn slice(&self, offset: usize, len: usize) -> Result<&[u8], DmaError> {
+        offset
+            .checked_add(len)
+            .and_then(|end| self.bytes.get(offset..end))
+            .ok_or(DmaError::Read { addr: self.base.wrapping_add(offset as u64), len })
+    }
+}
+
+/// Reads a block of memory fresh from the target in one operation, as a `MemView`.
+///
+/// Reading a structure field by field can tear, because the target keeps running
+/// between the reads and fields from different frames end up mixed. A snapshot
+/// bypasses the memprocfs cache and fetches the whole block in a single DMA request,
+/// so the fields within it were read within microseconds of each other. That makes
+/// them consistent for practical purposes, but it is not an atomic copy: the target CPU
+/// can still write to the block while the request is in flight, and no consistency
+/// holds between two separate snapshots.
+///
+/// # Arguments
+///
+/// * `process` - Reference to a `VmmProcess` instance representing the target process.
+/// * `addr` - Virtual address of the block.
+/// * `len` - Number of bytes to read.
+///
+/// # Returns
+///
+/// A `Result` containing the `MemView`, or `DmaError::Read` if fewer than `len` bytes
+/// could be read.
+///
+/// # Examples
+///
+/// ```ignore
+/// let player = snapshot(&process, local_player, 0x400)?;
+/// let position: [f32; 3] = player.get(0x138)?;
+/// let view_angles: [f32; 2] = player.get(0x1A0)?;
+/// ```
+pub fn snapshot(process: &VmmProcess, addr: u64, len: usize) -> Result<MemView, DmaError> {
+    Ok(MemView::new(addr, read_bytes_ex(process, addr, len, ReadFlags::no_cache())?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_reads_fields_at_offsets() {
+        let view = MemView::new(0x1000, vec![0x78, 0x56, 0x34, 0x12, 0x00, 0x00, 0x80, 0x3F]);
+        assert_eq!(view.get::<u32>(0).unwrap(), 0x1234_5678);
+        assert_eq!(view.get::<f32>(4).unwrap(), 1.0);
+        assert_eq!(view.get::<u16>(1).unwrap(), 0x3456);
+    }
+
+    #[test]
+    fn accesses_past_the_end_fail() {
+        let view = MemView::new(0x1000, vec![0; 8]);
+        assert!(matches!(view.get::<u64>(1), Err(DmaError::Read { addr: 0x1001, len: 8 })));
+        assert!(view.slice(8, 1).is_err());
+        assert!(view.slice(usize::MAX, 2).is_err());
+        assert_eq!(view.slice(8, 0).unwrap(), &[] as &[u8]);
+    }
+}